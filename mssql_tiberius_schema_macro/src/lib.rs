@@ -0,0 +1,173 @@
+//! `mssql_schema!` expands the structs of `mssql_tiberius_schema_parser` from a schema
+//! snapshot at compile time, so the generated code does not have to be committed.
+//!
+//! ```ignore
+//! // schema.json is written by `mssql_tiberius_schema_parser --save_snapshot schema.json`.
+//! mssql_schema!("schema.json", "jkrtdb.dbo.ritem", "jkrtdb.dbo.rcategory");
+//!
+//! // The [naming] of the CLI config, so the names are the same as the generated files.
+//! mssql_schema!("schema.json", "jkrtdb.dbo.ritem", naming = "config.toml");
+//! ```
+use std::path::PathBuf;
+
+use mssql_tiberius_schema_parser::{GeneratorOptions, NamingConfig, Schema};
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream, Parser},
+    punctuated::Punctuated,
+    Ident, LitStr, Token,
+};
+
+/// Expands the struct, constants and impls of the tables the same way as the `rust`
+/// generator in one file mode.
+///
+/// The first argument is the snapshot path relative to `CARGO_MANIFEST_DIR`, the others
+/// are `catalog.schema.table` names and an optional `naming = "config.toml"`, the config
+/// file of the CLI whose `[naming]` is used. Without it the names are the default naming,
+/// not the ones of a CLI run with a custom `[naming]`. The items are written in a private module and
+/// re-exported, so the `use` lines of the generated code do not clash with the caller's.
+#[proc_macro]
+pub fn mssql_schema(input: TokenStream) -> TokenStream {
+    match expand(input.into()) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// An argument after the snapshot path.
+enum Argument {
+    Table(LitStr),
+    Naming(LitStr),
+}
+
+impl Parse for Argument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(Argument::Table(input.parse()?));
+        }
+        let name: Ident = input.parse()?;
+        if name != "naming" {
+            return Err(syn::Error::new(
+                name.span(),
+                "expected \"catalog.schema.table\" or naming = \"config.toml\"",
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        Ok(Argument::Naming(input.parse()?))
+    }
+}
+
+fn expand(input: proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream> {
+    let arguments = Punctuated::<Argument, Token![,]>::parse_terminated.parse2(input)?;
+    let mut arguments = arguments.into_iter();
+    let snapshot = match arguments.next() {
+        Some(Argument::Table(snapshot)) => snapshot,
+        _ => {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "expected mssql_schema!(\"schema.json\", \"catalog.schema.table\", ..)",
+            ))
+        }
+    };
+    let mut table_names: Vec<LitStr> = vec![];
+    let mut naming_config: Option<LitStr> = None;
+    for argument in arguments {
+        match argument {
+            Argument::Table(table_name) => table_names.push(table_name),
+            Argument::Naming(config) if naming_config.is_none() => naming_config = Some(config),
+            Argument::Naming(config) => {
+                return Err(syn::Error::new(
+                    config.span(),
+                    "naming is set more than once",
+                ))
+            }
+        }
+    }
+    if table_names.is_empty() {
+        return Err(syn::Error::new(
+            snapshot.span(),
+            "expected at least one \"catalog.schema.table\" after the snapshot path",
+        ));
+    }
+
+    let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+    let snapshot_path = manifest_dir.join(snapshot.value());
+    let schema = Schema::from_snapshot(&snapshot_path)
+        .map_err(|error| syn::Error::new(snapshot.span(), error.to_string()))?;
+    let (naming, config_path) = match &naming_config {
+        Some(config) => {
+            let config_path = manifest_dir.join(config.value());
+            let naming = NamingConfig::from_config(&config_path)
+                .map_err(|error| syn::Error::new(config.span(), error.to_string()))?;
+            (naming, Some(config_path.to_string_lossy().to_string()))
+        }
+        None => (NamingConfig::default(), None),
+    };
+
+    for table_name in &table_names {
+        let found = schema.get_tables().iter().any(|table| {
+            table
+                .name
+                .get_sql_table_name()
+                .eq_ignore_ascii_case(&table_name.value())
+        });
+        if !found {
+            return Err(syn::Error::new(
+                table_name.span(),
+                format!("{} is not in {:?}", table_name.value(), snapshot_path),
+            ));
+        }
+    }
+
+    let file_list = schema
+        .filter(|table| {
+            table_names.iter().any(|table_name| {
+                table
+                    .name
+                    .get_sql_table_name()
+                    .eq_ignore_ascii_case(&table_name.value())
+            })
+        })
+        .naming(naming)
+        .generators(["rust"])
+        .options(GeneratorOptions {
+            path: Some("mssql_schema.rs".into()),
+            ..Default::default()
+        })
+        .generate()
+        .map_err(|error| syn::Error::new(snapshot.span(), error.to_string()))?;
+    let file: proc_macro2::TokenStream = file_list
+        .into_values()
+        .next()
+        .unwrap_or_default()
+        .parse()
+        .map_err(|error: proc_macro2::LexError| {
+            syn::Error::new(snapshot.span(), error.to_string())
+        })?;
+
+    let module_name = format_ident!(
+        "__mssql_schema_{}",
+        table_names
+            .iter()
+            .map(|table_name| table_name.value())
+            .collect::<Vec<String>>()
+            .join("_")
+            .to_lowercase()
+            .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+    );
+    let snapshot_path = snapshot_path.to_string_lossy().to_string();
+    let config_path = config_path.into_iter();
+    Ok(quote! {
+        // Rebuilds the caller when the snapshot or the config changes.
+        const _: &[u8] = include_bytes!(#snapshot_path);
+        #(const _: &[u8] = include_bytes!(#config_path);)*
+
+        #[doc(hidden)]
+        #[allow(non_snake_case, dead_code)]
+        mod #module_name {
+            #file
+        }
+        pub use #module_name::*;
+    })
+}
@@ -0,0 +1,14 @@
+use mssql_tiberius_schema_macro::mssql_schema;
+
+mssql_schema!("tests/schema.json", "jkrtdb.dbo.ritem");
+
+#[test]
+fn test_mssql_schema() {
+    let ritem = JkrtdbDboRitem {
+        ItemNo: 1,
+        Item_Name: Some("coffee".into()),
+    };
+    assert_eq!(ritem.clone().ItemNo, 1);
+    assert_eq!(RITEM_TABLE_NAME, "jkrtdb.dbo.ritem");
+    assert!(RITEM_COLUMNS.contains("[Item Name]"));
+}
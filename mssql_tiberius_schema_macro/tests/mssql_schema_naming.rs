@@ -0,0 +1,17 @@
+use mssql_tiberius_schema_macro::mssql_schema;
+
+mssql_schema!(
+    "tests/schema.json",
+    "jkrtdb.dbo.ritem",
+    naming = "tests/naming.toml"
+);
+
+#[test]
+fn test_mssql_schema_naming() {
+    let ritem = DboRitem {
+        item_no: 1,
+        item_name: Some("coffee".into()),
+    };
+    assert_eq!(ritem.clone().item_no, 1);
+    assert!(RITEM_COLUMNS.contains("[Item Name]"));
+}
@@ -1,4 +1,8 @@
-use crate::helpers::config::{STRUCT_FILE_NAME, STRUCT_SIGNAL_FILE_NAME};
+use crate::helpers::config::{
+    PROTO_LOCK_FILE_NAME, STRUCT_CSHARP_FILE_NAME, STRUCT_DART_FILE_NAME, STRUCT_FILE_NAME,
+    STRUCT_PROTO_FILE_NAME, STRUCT_PYTHON_FILE_NAME, STRUCT_SIGNAL_FILE_NAME,
+    STRUCT_TYPESCRIPT_FILE_NAME,
+};
 use clap::{Parser, ValueEnum};
 use serde::{Deserialize, Serialize};
 use strum::AsRefStr;
@@ -84,6 +88,14 @@ pub struct Cli {
     )]
     pub use_split_file: bool,
 
+    #[arg(
+        long = "use_reexport",
+        value_name = "USE REEXPORT",
+        help = "Use pub use of the structs in mod.rs of split files. add cli option with --use_reexport.\n- Defaults to false",
+        default_value = "false"
+    )]
+    pub use_reexport: bool,
+
     #[arg(
         long = "path",
         value_name = "PATH",
@@ -98,6 +110,151 @@ pub struct Cli {
     )]
     pub signal_path: Option<String>,
 
+    #[arg(
+        long = "typescript_path",
+        value_name = "TYPESCRIPT PATH",
+        help = format!("The path to the ts file of the typescript generator.\n- Defaults to {}", STRUCT_TYPESCRIPT_FILE_NAME)
+    )]
+    pub typescript_path: Option<String>,
+
+    #[arg(
+        long = "dart_path",
+        value_name = "DART PATH",
+        help = format!("The path to the dart file of the dart generator.\n- Defaults to {}", STRUCT_DART_FILE_NAME)
+    )]
+    pub dart_path: Option<String>,
+
+    #[arg(
+        long = "use_freezed",
+        value_name = "USE FREEZED",
+        help = "Use freezed classes instead of json_serializable classes in the dart generator. add cli option with --use_freezed.\n- Defaults to false",
+        default_value = "false"
+    )]
+    pub use_freezed: bool,
+
+    #[arg(
+        long = "proto_path",
+        value_name = "PROTO PATH",
+        help = format!("The path to the proto file of the proto generator.\n- Defaults to {}", STRUCT_PROTO_FILE_NAME)
+    )]
+    pub proto_path: Option<String>,
+
+    #[arg(
+        long = "proto_package",
+        value_name = "PROTO PACKAGE",
+        help = "The package of the proto files.\n- Defaults no package."
+    )]
+    pub proto_package: Option<String>,
+
+    #[arg(
+        long = "proto_lock_path",
+        value_name = "PROTO LOCK PATH",
+        help = format!("The path to the lock file of proto field numbers.\n- Defaults to {}", PROTO_LOCK_FILE_NAME)
+    )]
+    pub proto_lock_path: Option<String>,
+
+    #[arg(
+        long = "json_schema_path",
+        value_name = "JSON SCHEMA PATH",
+        help = "The directory of the json schema files and openapi.json of the json_schema generator.\n- Defaults to json_schema"
+    )]
+    pub json_schema_path: Option<String>,
+
+    #[arg(
+        long = "csharp_path",
+        value_name = "CSHARP PATH",
+        help = format!("The path to the cs file of the csharp generator.\n- Defaults to {}", STRUCT_CSHARP_FILE_NAME)
+    )]
+    pub csharp_path: Option<String>,
+
+    #[arg(
+        long = "csharp_namespace",
+        value_name = "CSHARP NAMESPACE",
+        help = "The file scoped namespace of the cs files.\n- Defaults no namespace."
+    )]
+    pub csharp_namespace: Option<String>,
+
+    #[arg(
+        long = "use_csharp_record",
+        value_name = "USE CSHARP RECORD",
+        help = "Use records with init properties instead of classes in the csharp generator. add cli option with --use_csharp_record.\n- Defaults to false",
+        default_value = "false"
+    )]
+    pub use_csharp_record: bool,
+
+    #[arg(
+        long = "python_path",
+        value_name = "PYTHON PATH",
+        help = format!("The path to the py file of the python generator.\n- Defaults to {}", STRUCT_PYTHON_FILE_NAME)
+    )]
+    pub python_path: Option<String>,
+
+    #[arg(
+        long = "use_python_dataclass",
+        value_name = "USE PYTHON DATACLASS",
+        help = "Use dataclasses instead of pydantic models in the python generator. add cli option with --use_python_dataclass.\n- Defaults to false",
+        default_value = "false"
+    )]
+    pub use_python_dataclass: bool,
+
+    #[arg(
+        long = "docs_path",
+        value_name = "DOCS PATH",
+        help = "The directory of the data dictionary pages of the docs generator.\n- Defaults to docs"
+    )]
+    pub docs_path: Option<String>,
+
+    #[arg(
+        long = "docs_format",
+        value_name = "DOCS FORMAT",
+        help = format!("The format of the data dictionary pages.\n- Defaults to {}", DocsFormat::Markdown.as_ref()),
+        default_value_t = DocsFormat::Markdown
+    )]
+    #[clap(value_enum, default_value_t=DocsFormat::Markdown)]
+    pub docs_format: DocsFormat,
+
+    #[arg(
+        long = "diagram_path",
+        value_name = "DIAGRAM PATH",
+        help = "The directory of the er diagrams of the diagram generator.\n- Defaults to diagrams"
+    )]
+    pub diagram_path: Option<String>,
+
+    #[arg(
+        long = "diagram_formats",
+        value_name = "DIAGRAM FORMATS",
+        help = "The formats of the er diagrams, separated by commas.\n- Defaults to mermaid",
+        value_delimiter = ',',
+        default_value = "mermaid"
+    )]
+    #[clap(value_enum)]
+    pub diagram_formats: Vec<DiagramFormat>,
+
+    #[arg(
+        long = "diagram_group",
+        value_name = "DIAGRAM GROUP",
+        help = format!("Write an er diagram per database or per split_directory.\n- Defaults to {}", DiagramGroup::Database.as_ref()),
+        default_value_t = DiagramGroup::Database
+    )]
+    #[clap(value_enum, default_value_t=DiagramGroup::Database)]
+    pub diagram_group: DiagramGroup,
+
+    #[arg(
+        long = "diagram_tables",
+        value_name = "DIAGRAM TABLES",
+        help = "Table names(jkrtdb_dbo_ritem) of the er diagrams, separated by commas. A trailing * matches the names starting with it(jkrtdb_dbo_r*).\n- Defaults to every table",
+        value_delimiter = ','
+    )]
+    pub diagram_tables: Option<Vec<String>>,
+
+    #[arg(
+        long = "diagram_keys_only",
+        value_name = "DIAGRAM KEYS ONLY",
+        help = "Show only primary and foreign key columns in the er diagrams. add cli option with --diagram_keys_only.\n- Defaults to false",
+        default_value = "false"
+    )]
+    pub diagram_keys_only: bool,
+
     #[arg(
         long = "config_path",
         value_name = "CONFIG PATH",
@@ -112,6 +269,53 @@ pub struct Cli {
         default_value = "true"
     )]
     pub use_insert_query: bool,
+
+    #[arg(
+        long = "use_try_from_row",
+        value_name = "USE TRY FROM ROW",
+        help = "Use TryFrom<&tiberius::Row> impl. add cli option with --use_try_from_row.\n- Defaults to false",
+        default_value = "false"
+    )]
+    pub use_try_from_row: bool,
+
+    #[arg(
+        long = "use_crud_query",
+        value_name = "USE CRUD QUERY",
+        help = "Use select, insert, update, upsert and delete functions. add cli option with --use_crud_query.\n- Defaults to false",
+        default_value = "false"
+    )]
+    pub use_crud_query: bool,
+
+    #[arg(
+        long = "use_bulk_insert",
+        value_name = "USE BULK INSERT",
+        help = "Use try_into_row and bulk_insert functions. add cli option with --use_bulk_insert.\n- Defaults to false",
+        default_value = "false"
+    )]
+    pub use_bulk_insert: bool,
+
+    #[arg(
+        long = "generators",
+        value_name = "GENERATORS",
+        help = "Generators to run, separated by commas. rust, signals, templates, typescript, dart, proto, json_schema, csharp, python, docs, diagram.\n- Defaults to rust,signals,templates",
+        value_delimiter = ',',
+        default_value = "rust,signals,templates"
+    )]
+    pub generators: Vec<String>,
+
+    #[arg(
+        long = "save_snapshot",
+        value_name = "SAVE SNAPSHOT",
+        help = "The path to save the tables as json, for load_snapshot and the mssql_schema! macro.\n- Defaults no name specified."
+    )]
+    pub save_snapshot: Option<String>,
+
+    #[arg(
+        long = "load_snapshot",
+        value_name = "LOAD SNAPSHOT",
+        help = "The path of a json saved by save_snapshot, used instead of connecting to the server.\n- Defaults no name specified."
+    )]
+    pub load_snapshot: Option<String>,
 }
 
 impl Cli {
@@ -127,10 +331,39 @@ impl Cli {
             _type: self._type,
             use_signal_parser: self.use_signal_parser,
             use_split_file: self.use_split_file,
+            use_reexport: self.use_reexport,
             use_insert_query: self.use_insert_query,
+            use_try_from_row: self.use_try_from_row,
+            use_crud_query: self.use_crud_query,
+            use_bulk_insert: self.use_bulk_insert,
             path: self.path,
             signal_path: self.signal_path,
+            typescript_path: self.typescript_path,
+            dart_path: self.dart_path,
+            use_freezed: self.use_freezed,
+            proto_path: self.proto_path,
+            proto_package: self.proto_package,
+            proto_lock_path: self.proto_lock_path,
+            json_schema_path: self.json_schema_path,
+            csharp_path: self.csharp_path,
+            csharp_namespace: self.csharp_namespace,
+            use_csharp_record: self.use_csharp_record,
+            python_path: self.python_path,
+            use_python_dataclass: self.use_python_dataclass,
+            docs_path: self.docs_path,
+            docs_format: self.docs_format,
+            diagram_path: self.diagram_path,
+            diagram_formats: self.diagram_formats,
+            diagram_group: self.diagram_group,
+            diagram_tables: self.diagram_tables,
+            diagram_keys_only: self.diagram_keys_only,
             database: None,
+            naming: None,
+            template: None,
+            enums: None,
+            generators: self.generators,
+            save_snapshot: self.save_snapshot,
+            load_snapshot: self.load_snapshot,
         }
     }
 }
@@ -145,3 +378,40 @@ pub enum AuthType {
     #[value(help = "Use SQL Server Authentication")]
     ServerAuth,
 }
+
+#[derive(ValueEnum, Clone, Debug, Default, AsRefStr, Deserialize, Serialize)]
+#[value(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DocsFormat {
+    #[default]
+    #[value(help = "Write markdown pages")]
+    Markdown,
+    #[value(help = "Write html pages")]
+    Html,
+}
+
+#[derive(ValueEnum, Clone, Debug, AsRefStr, Deserialize, Serialize, PartialEq)]
+#[value(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DiagramFormat {
+    #[value(help = "Write mermaid erDiagram(.mmd)")]
+    Mermaid,
+    #[value(help = "Write graphviz dot(.dot)")]
+    Dot,
+    #[value(help = "Write plantuml(.puml)")]
+    Plantuml,
+}
+
+#[derive(ValueEnum, Clone, Debug, Default, AsRefStr, Deserialize, Serialize)]
+#[value(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DiagramGroup {
+    #[default]
+    #[value(help = "Write a diagram per database")]
+    Database,
+    #[value(help = "Write a diagram per split_directory, other tables per database")]
+    SplitDirectory,
+}
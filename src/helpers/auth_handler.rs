@@ -2,11 +2,15 @@ use std::{collections::HashMap, sync::Arc};
 
 use crate::helpers::{
     args_parser::{AuthType, Cli},
+    common::quote_sql_identifier,
     get_database_tables::get_database_tables,
     get_table_schema::GetTableSchema,
-    rs_file_writer::rs_file_writer,
-    signal_file_writer::signal_file_writer,
-    structs::{ColumnName, InnerArgs, Table, TableConfig, TableName},
+    schema::Schema,
+    structs::{
+        ColumnName, ColumnProperty, DescriptionProperty, EnumConfig, ForeignKey, ForeignKeyColumn,
+        InnerArgs, KeyColumn, LookupEnum, LookupEnumValue, Table, TableIdents, TableName,
+        TableOptions,
+    },
     traits::{select_parser::SelectParserTrait, StringUtil},
 };
 use gethostname::gethostname;
@@ -24,10 +28,30 @@ fn test_parse_toml() {
     println!("{:?}", toml::from_str::<InnerArgs>(&config).unwrap());
 }
 
-pub async fn auth_handler(args: Cli) -> Result<(), Box<dyn std::error::Error>> {
-    let mut config = Config::new();
-    let hostname = gethostname();
+#[test]
+fn test_get_description() {
+    let descriptions: Vec<DescriptionProperty> = serde_json::from_value(serde_json::json!([
+        { "TABLE_SCHEMA": "dbo", "TABLE_NAME": "ritem", "COLUMN_NAME": null, "DESCRIPTION": "Menu items" },
+        { "TABLE_SCHEMA": "dbo", "TABLE_NAME": "ritem", "COLUMN_NAME": "ItemNo", "DESCRIPTION": " " },
+        { "TABLE_SCHEMA": "sales", "TABLE_NAME": "ritem", "COLUMN_NAME": null, "DESCRIPTION": "Sales items" },
+    ]))
+    .unwrap();
+    let description_index = get_description_index(&descriptions);
+    assert_eq!(
+        get_description(&description_index, "dbo", "ritem", None).as_deref(),
+        Some("Menu items")
+    );
+    assert_eq!(
+        get_description(&description_index, "dbo", "ritem", Some("ItemNo")),
+        None
+    );
+    assert_eq!(
+        get_description(&description_index, "sales", "ritem", None).as_deref(),
+        Some("Sales items")
+    );
+}
 
+pub async fn auth_handler(args: Cli) -> Result<(), Box<dyn std::error::Error>> {
     let args = match args.config_path {
         Some(config_path) => {
             let config = std::fs::read_to_string(config_path)?;
@@ -35,6 +59,25 @@ pub async fn auth_handler(args: Cli) -> Result<(), Box<dyn std::error::Error>> {
         }
         None => args.to_inner_args(),
     };
+    let save_snapshot = args.save_snapshot.clone();
+
+    let schema = match args.load_snapshot.clone() {
+        Some(load_snapshot) => Schema::from_args(args)?.load_snapshot(load_snapshot)?,
+        None => Schema::connect(args).await?,
+    };
+    if let Some(save_snapshot) = save_snapshot {
+        schema.save_snapshot(save_snapshot)?;
+    }
+    schema.write()?;
+    Ok(())
+}
+
+/// Connects with `args` and reads the tables of every user database, with the
+/// table options of `[database]` applied.
+pub async fn get_tables(args: &InnerArgs) -> Result<Vec<Table>, Box<dyn std::error::Error>> {
+    let mut config = Config::new();
+    let hostname = gethostname();
+
     match args._type {
         AuthType::WinAuth => {
             config.authentication(AuthMethod::windows(
@@ -47,11 +90,11 @@ pub async fn auth_handler(args: Cli) -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    if let Some(database_name) = args.database_name {
+    if let Some(database_name) = &args.database_name {
         config.database(database_name);
     }
 
-    match args.instance_name {
+    match &args.instance_name {
         Some(instance_name) => {
             config.instance_name(instance_name);
         }
@@ -129,69 +172,236 @@ pub async fn auth_handler(args: Cli) -> Result<(), Box<dyn std::error::Error>> {
     }
     drop(tx);
 
-    let mut tables_options: HashMap<String, TableConfig> = HashMap::new();
-    let mut use_import_special = false;
-    let mut split_directory = vec![];
-    if let Some(database) = args.database {
-        use_import_special = database.use_import_special;
-        if let Some(tables) = database.tables {
-            tables.into_iter().for_each(|table| {
-                tables_options.insert(table.table_name.copy_string(), table);
-            });
-        }
-        if let Some(split_directories) = database.split_directory {
-            split_directory = split_directories;
-        }
-    }
+    let table_options = TableOptions::from_args(args);
 
     let mut table_list: Vec<Table> = vec![];
-    while let Some((tables, columns)) = rx.recv().await {
-        let table_names = tables.get_table_schema::<TableName>();
-        let column_names = columns.get_table_schema::<ColumnName>();
+    while let Some(database_schema) = rx.recv().await {
+        let table_names = database_schema.tables.get_table_schema::<TableName>()?;
+        let column_names = database_schema.columns.get_table_schema::<ColumnName>()?;
+        let mut key_columns = database_schema
+            .key_columns
+            .get_table_schema::<KeyColumn>()?;
+        key_columns.sort_by_key(|key_column| key_column.ordinal_position);
+        let column_properties = database_schema
+            .column_properties
+            .get_table_schema::<ColumnProperty>()?;
+        let mut foreign_key_columns = database_schema
+            .foreign_key_columns
+            .get_table_schema::<ForeignKeyColumn>()?;
+        foreign_key_columns.sort_by_key(|foreign_key_column| foreign_key_column.ordinal_position);
+        let descriptions = database_schema
+            .descriptions
+            .get_table_schema::<DescriptionProperty>()?;
 
-        for table_name in table_names {
-            if use_import_special && !tables_options.contains_key(&table_name.get_file_name()) {
+        // Indexes by table and column, so loading a large database stays linear.
+        let mut columns_by_table: HashMap<(&str, &str), Vec<&ColumnName>> = HashMap::new();
+        for column_name in &column_names {
+            columns_by_table
+                .entry((&column_name.table_schema, &column_name.table_name))
+                .or_default()
+                .push(column_name);
+        }
+        let mut key_columns_by_table: HashMap<(&str, &str), Vec<String>> = HashMap::new();
+        for key_column in &key_columns {
+            key_columns_by_table
+                .entry((&key_column.table_schema, &key_column.table_name))
+                .or_default()
+                .push(key_column.column_name.copy_string());
+        }
+        let mut foreign_key_columns_by_table: HashMap<(&str, &str), Vec<&ForeignKeyColumn>> =
+            HashMap::new();
+        for foreign_key_column in &foreign_key_columns {
+            foreign_key_columns_by_table
+                .entry((
+                    &foreign_key_column.table_schema,
+                    &foreign_key_column.table_name,
+                ))
+                .or_default()
+                .push(foreign_key_column);
+        }
+        let column_properties: HashMap<(&str, &str, &str), &ColumnProperty> = column_properties
+            .iter()
+            .map(|property| {
+                (
+                    (
+                        property.table_schema.as_str(),
+                        property.table_name.as_str(),
+                        property.column_name.as_str(),
+                    ),
+                    property,
+                )
+            })
+            .collect();
+        let descriptions = get_description_index(&descriptions);
+
+        for table_name in &table_names {
+            if !table_options.is_imported(table_name) {
                 continue;
             }
+            let table_key = (
+                table_name.table_schema.as_str(),
+                table_name.table_name.as_str(),
+            );
             let mut table = Table {
                 name: table_name.clone(),
                 columns: vec![],
-                use_signal_parser: match tables_options.get(&table_name.get_file_name()) {
-                    Some(table_config) => table_config.use_signal_parser,
-                    None => false,
-                },
-                use_signal_file: match tables_options.get(&table_name.get_file_name()) {
-                    Some(table_config) => table_config.use_signal_file,
-                    None => true,
-                },
-                use_insert_query: match tables_options.get(&table_name.get_file_name()) {
-                    Some(table_config) => table_config.use_insert_query,
-                    None => true,
-                },
+                primary_keys: key_columns_by_table
+                    .get(&table_key)
+                    .cloned()
+                    .unwrap_or_default(),
+                foreign_keys: get_foreign_keys(
+                    foreign_key_columns_by_table
+                        .get(&table_key)
+                        .map_or(&[], |columns| columns.as_slice()),
+                ),
+                use_signal_parser: false,
+                use_signal_file: true,
+                use_dart_file: true,
+                use_insert_query: true,
+                use_try_from_row: false,
+                use_crud_query: false,
+                use_bulk_insert: false,
+                description: get_description(
+                    &descriptions,
+                    &table_name.table_schema,
+                    &table_name.table_name,
+                    None,
+                ),
+                lookup_enum: None,
+                idents: TableIdents::default(),
             };
-            column_names.iter().for_each(|column_name| {
-                if &table_name.table_name == &column_name.table_name {
-                    table.columns.push(column_name.clone());
+            table_options.apply(&mut table);
+            for column_name in columns_by_table.get(&table_key).into_iter().flatten() {
+                let mut column_name = (*column_name).clone();
+                if let Some(column_property) = column_properties.get(&(
+                    column_name.table_schema.as_str(),
+                    column_name.table_name.as_str(),
+                    column_name.column_name.as_str(),
+                )) {
+                    column_name.is_identity = column_property.is_identity;
+                    column_name.is_computed = column_property.is_computed;
                 }
-            });
+                column_name.description = get_description(
+                    &descriptions,
+                    &column_name.table_schema,
+                    &column_name.table_name,
+                    Some(&column_name.column_name),
+                );
+                table.columns.push(column_name);
+            }
             table_list.push(table);
         }
     }
 
-    rs_file_writer(
-        &args.path,
-        args.use_split_file,
-        &table_list,
-        &split_directory,
-    )
-    .await?;
-
-    signal_file_writer(
-        &args.signal_path,
-        args.use_split_file,
-        &table_list,
-        &split_directory,
-    )
-    .await?;
-    Ok(())
+    if let Some(enums) = &args.enums {
+        let mut client = client.lock().await;
+        for enum_config in enums {
+            let Some(table) = table_list
+                .iter_mut()
+                .find(|table| is_enum_table(table, enum_config))
+            else {
+                return Err(format!(
+                    "enums table {} is not in the tables",
+                    enum_config.table
+                ))?;
+            };
+            let values = client
+                .simple_query(format!(
+                    "SELECT
+                        CAST({0} AS nvarchar(max)) AS [KEY],
+                        CAST({1} AS nvarchar(max)) AS [NAME]
+                    FROM {2}.{3}.{4}
+                    ORDER BY {0}
+                    ",
+                    quote_sql_identifier(&enum_config.key),
+                    quote_sql_identifier(&enum_config.name),
+                    quote_sql_identifier(&table.name.table_catalog),
+                    quote_sql_identifier(&table.name.table_schema),
+                    quote_sql_identifier(&table.name.table_name)
+                ))
+                .await?
+                .into_results()
+                .await?
+                .select_parser()
+                .get_table_schema::<LookupEnumValue>()?;
+            table.lookup_enum = Some(LookupEnum {
+                key: enum_config.key.copy_string(),
+                name: enum_config.name.copy_string(),
+                enum_name: enum_config.enum_name.clone(),
+                values,
+            });
+        }
+    }
+
+    table_list.sort_by_key(|table| table.name.get_config_name());
+    Ok(table_list)
+}
+
+/// `table` of `[[enums]]` is the SQL name or the config name of the table.
+fn is_enum_table(table: &Table, enum_config: &EnumConfig) -> bool {
+    table.name.get_config_name() == enum_config.table.to_lowercase()
+        || table
+            .name
+            .get_sql_table_name()
+            .eq_ignore_ascii_case(&enum_config.table)
+}
+
+/// `MS_Description` by `(table_schema, table_name, column_name)`, the column name
+/// is `None` for the table.
+fn get_description_index(
+    descriptions: &[DescriptionProperty],
+) -> HashMap<(&str, &str, Option<&str>), &str> {
+    let mut description_index = HashMap::new();
+    for description in descriptions {
+        description_index
+            .entry((
+                description.table_schema.as_str(),
+                description.table_name.as_str(),
+                description.column_name.as_deref(),
+            ))
+            .or_insert(description.description.as_deref().unwrap_or_default());
+    }
+    description_index
+}
+
+/// `MS_Description` of the table, or of its column when `column_name` is set.
+fn get_description<'a>(
+    description_index: &HashMap<(&'a str, &'a str, Option<&'a str>), &'a str>,
+    table_schema: &'a str,
+    table_name: &'a str,
+    column_name: Option<&'a str>,
+) -> Option<String> {
+    description_index
+        .get(&(table_schema, table_name, column_name))
+        .filter(|description| !description.trim().is_empty())
+        .map(|description| description.to_string())
+}
+
+/// Groups the foreign key columns of a table by constraint.
+fn get_foreign_keys(foreign_key_columns: &[&ForeignKeyColumn]) -> Vec<ForeignKey> {
+    let mut foreign_keys: Vec<ForeignKey> = vec![];
+    for foreign_key_column in foreign_key_columns {
+        match foreign_keys
+            .iter_mut()
+            .find(|foreign_key| foreign_key.name == foreign_key_column.constraint_name)
+        {
+            Some(foreign_key) => {
+                foreign_key
+                    .columns
+                    .push(foreign_key_column.column_name.copy_string());
+                foreign_key
+                    .referenced_columns
+                    .push(foreign_key_column.referenced_column_name.copy_string());
+            }
+            None => foreign_keys.push(ForeignKey {
+                name: foreign_key_column.constraint_name.copy_string(),
+                columns: vec![foreign_key_column.column_name.copy_string()],
+                referenced_table_schema: foreign_key_column.referenced_table_schema.copy_string(),
+                referenced_table_name: foreign_key_column.referenced_table_name.copy_string(),
+                referenced_columns: vec![foreign_key_column.referenced_column_name.copy_string()],
+            }),
+        }
+    }
+    foreign_keys.sort_by(|a, b| a.name.cmp(&b.name));
+    foreign_keys
 }
@@ -1,4 +1,105 @@
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+
+use crate::helpers::structs::{
+    ColumnName, NameCase, NamingConfig, SplitDirectoryConfig, Table, UnicodeMode,
+};
+
+const RUST_KEYWORDS: [&str; 52] = [
+    "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+    "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "async", "await", "dyn", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield", "try", "gen",
+];
+
+#[test]
+fn test_make_mod_files() {
+    let root = PathBuf::from("sample");
+    let module_list = vec![
+        (
+            root.join("jk_retail").join("ritem.rs"),
+            vec!["JkttdbDboRitem".into()],
+        ),
+        (
+            root.join("jk_retail").join("type.rs"),
+            vec!["JkttdbDboType".into()],
+        ),
+        (root.join("rcategory.rs"), vec!["JkrtdbDboRcategory".into()]),
+    ];
+    let file_list = make_mod_files(&root, &module_list, false);
+    assert_eq!(
+        file_list[&root.join("mod.rs")],
+        "pub mod jk_retail;\npub mod rcategory;\n"
+    );
+    assert_eq!(
+        file_list[&root.join("jk_retail").join("mod.rs")],
+        "pub mod ritem;\npub mod r#type;\n"
+    );
+
+    let file_list = make_mod_files(&root, &module_list, true);
+    assert!(file_list[&root.join("mod.rs")]
+        .ends_with("\npub use jk_retail::{JkttdbDboRitem, JkttdbDboType};\npub use rcategory::JkrtdbDboRcategory;\n"));
+
+    // split_directory names are written as they are, the module keeps the path.
+    let module_list = vec![
+        (
+            root.join("2024").join("self.rs"),
+            vec!["JkrtdbDboSelf".into()],
+        ),
+        (root.join("1abc.rs"), vec!["Jkrtdb1abc".into()]),
+    ];
+    let file_list = make_mod_files(&root, &module_list, true);
+    assert_eq!(
+        file_list[&root.join("mod.rs")],
+        "#[path = \"1abc.rs\"]\npub mod _1abc;\n#[path = \"2024/mod.rs\"]\npub mod _2024;\n\npub use _1abc::Jkrtdb1abc;\npub use _2024::JkrtdbDboSelf;\n"
+    );
+    assert_eq!(
+        file_list[&root.join("2024").join("mod.rs")],
+        "#[path = \"self.rs\"]\npub mod self_;\n\npub use self_::JkrtdbDboSelf;\n"
+    );
+    format_rust_file(Path::new("mod.rs"), &file_list[&root.join("mod.rs")]).unwrap();
+}
+
+#[test]
+fn test_format_rust_file() {
+    let path = Path::new("structs.rs");
+    let file = format_rust_file(path, "pub struct Ritem {pub ItemNo: i32,}\n\n\npub const RITEM_TABLE_NAME: &'static str = \"jkrtdb.dbo.ritem\";").unwrap();
+    assert_eq!(
+        file,
+        "pub struct Ritem {\n    pub ItemNo: i32,\n}\npub const RITEM_TABLE_NAME: &'static str = \"jkrtdb.dbo.ritem\";\n"
+    );
+    let error = format_rust_file(path, "pub struct Ritem { pub ItemNo: i32 ").unwrap_err();
+    assert!(error.to_string().contains("\"structs.rs\""));
+}
+
+#[test]
+fn test_unparse_items() {
+    let struct_ident = make_ident("JkrtdbDboRitem");
+    let struct_docs = make_doc_attrs(&Some("Menu items \r\n\r\nof the POS".into()));
+    let field_ident = make_ident("r#type");
+    let field_type = make_type("Option<Vec<u8>>");
+    assert_eq!(
+        unparse_items(quote! {
+            #(#struct_docs)*
+            pub struct #struct_ident {
+                pub #field_ident: #field_type,
+            }
+        }),
+        "/// Menu items\n///\n/// of the POS\npub struct JkrtdbDboRitem {\n    pub r#type: Option<Vec<u8>>,\n}\n\n"
+    );
+}
+
+/// Keywords that can not be written as raw identifiers.
+const NON_RAW_KEYWORDS: [&str; 5] = ["self", "Self", "crate", "super", "_"];
 
 pub fn convert_text_first_char_to_uppercase(text: &str) -> String {
     let mut result = String::new();
@@ -14,41 +115,320 @@ pub fn convert_text_first_char_to_uppercase(text: &str) -> String {
     result
 }
 
-pub fn get_table_names(table: &crate::helpers::structs::Table) -> (String, String, String) {
-    let table_name = table.name.get_table_name();
-    let file_name = table.name.get_file_name();
-    let sql_table_name = table.name.get_sql_table_name();
-    (table_name, file_name, sql_table_name)
+/// Applies `naming.dictionary` and `naming.unicode` to a SQL name.
+pub fn convert_unicode(text: &str, naming: &NamingConfig) -> String {
+    let text = match naming.dictionary.get(text) {
+        Some(name) => name.as_str(),
+        None => text,
+    };
+    match naming.unicode {
+        UnicodeMode::Replace => text.into(),
+        UnicodeMode::Keep => text
+            .chars()
+            .enumerate()
+            .map(|(index, c)| match c.is_ascii() {
+                true => c,
+                false if index == 0 && unicode_ident::is_xid_start(c) => c,
+                false if index != 0 && unicode_ident::is_xid_continue(c) => c,
+                false => '_',
+            })
+            .collect(),
+        UnicodeMode::Transliterate => deunicode::deunicode(text),
+    }
 }
 
-pub async fn write_files(
-    file_list: std::collections::HashMap<PathBuf, String>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    use tokio::io::AsyncWriteExt;
+pub fn is_identifier_char(c: char, naming: &NamingConfig) -> bool {
+    c.is_ascii_alphanumeric()
+        || c == '_'
+        || (naming.unicode == UnicodeMode::Keep && unicode_ident::is_xid_continue(c))
+}
+
+/// Converts a SQL name to chars that are valid in Rust identifiers.
+pub fn convert_identifier_chars(text: &str, naming: &NamingConfig) -> String {
+    convert_unicode(text, naming)
+        .chars()
+        .map(|c| match is_identifier_char(c, naming) {
+            true => c,
+            false => '_',
+        })
+        .collect()
+}
 
-    let (tx, mut rx) = tokio::sync::mpsc::channel(32);
+/// Converts a SQL name to ASCII chars for file and module names, which rustc only
+/// loads as ASCII. `keep` transliterates like `transliterate`.
+pub fn convert_file_name_chars(text: &str, naming: &NamingConfig) -> String {
+    let text = match naming.dictionary.get(text) {
+        Some(name) => name.as_str(),
+        None => text,
+    };
+    let text = match naming.unicode {
+        UnicodeMode::Replace => text.to_string(),
+        UnicodeMode::Keep | UnicodeMode::Transliterate => deunicode::deunicode(text),
+    };
+    text.chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c,
+            false => '_',
+        })
+        .collect()
+}
 
-    for (file_name, file) in file_list {
-        let folder_path = match file_name.is_dir() {
-            true => file_name.to_path_buf(),
-            false => file_name.parent().unwrap().to_path_buf(),
-        };
-        if !folder_path.exists() {
-            tokio::fs::create_dir_all(folder_path).await?;
+/// Joins the name parts and converts them to the given case.
+pub fn convert_case(parts: &[impl AsRef<str>], case: &NameCase) -> String {
+    let parts: Vec<&str> = parts.iter().map(|part| part.as_ref()).collect();
+    let text = parts.join("_");
+    match case {
+        NameCase::UpperFirst => parts
+            .iter()
+            .map(|part| convert_text_first_char_to_uppercase(part))
+            .collect(),
+        NameCase::PascalCase => text.to_upper_camel_case(),
+        NameCase::CamelCase => text.to_lower_camel_case(),
+        NameCase::SnakeCase => text.to_snake_case(),
+        NameCase::ScreamingSnakeCase => text.to_shouty_snake_case(),
+        NameCase::Lowercase => text.to_lowercase(),
+        NameCase::Uppercase => text.to_uppercase(),
+    }
+}
+
+pub fn is_rust_keyword(text: &str) -> bool {
+    RUST_KEYWORDS.contains(&text) || text == "_"
+}
+
+pub fn escape_rust_keyword(text: &str, naming: &NamingConfig) -> String {
+    if !is_rust_keyword(text) {
+        return text.into();
+    }
+    match &naming.keyword_suffix {
+        Some(suffix) => format!("{}{}", text, suffix),
+        None => match NON_RAW_KEYWORDS.contains(&text) {
+            true => format!("{}_", text),
+            false => format!("r#{}", text),
+        },
+    }
+}
+
+/// Quotes a name as a bracketed SQL Server identifier, `Unit Price` -> `[Unit Price]`.
+pub fn quote_sql_identifier(text: &str) -> String {
+    format!("[{}]", text.replace(']', "]]"))
+}
+
+/// Returns `mod.rs` of `root` and every directory between it and the files, with
+/// `pub mod` of each child and, when `use_reexport`, `pub use` of the given names.
+pub fn make_mod_files(
+    root: &Path,
+    module_list: &[(PathBuf, Vec<String>)],
+    use_reexport: bool,
+) -> HashMap<PathBuf, String> {
+    let mut tree: BTreeMap<PathBuf, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+    tree.entry(root.to_path_buf()).or_default();
+    for (file_path, names) in module_list {
+        let mut path = file_path.with_extension("");
+        while let Some(parent) = path.parent() {
+            if !parent.starts_with(root) {
+                break;
+            }
+            let module_name = path.file_name().unwrap().to_string_lossy().to_string();
+            tree.entry(parent.to_path_buf())
+                .or_default()
+                .entry(module_name)
+                .or_default()
+                .extend(names.iter().cloned());
+            path = parent.to_path_buf();
+        }
+    }
+
+    let mut file_list = HashMap::new();
+    for (directory, modules) in &tree {
+        let mut file = String::new();
+        for module_name in modules.keys() {
+            let module_ident = get_module_ident(module_name);
+            // A module named other than its file, `split_directory` names are not
+            // converted, needs the path of the file.
+            if module_ident.trim_start_matches("r#") != module_name {
+                let module_path = match tree.contains_key(&directory.join(module_name)) {
+                    true => format!("{}/mod.rs", module_name),
+                    false => format!("{}.rs", module_name),
+                };
+                file.push_str(&format!("#[path = {:?}]\n", module_path));
+            }
+            file.push_str(&format!("pub mod {};\n", module_ident));
         }
-        let tx_copy = tx.clone();
-        tokio::spawn(async move {
-            let result = tokio::fs::File::create(file_name).await?;
-            let mut writer: tokio::io::BufWriter<tokio::fs::File> =
-                tokio::io::BufWriter::new(result);
-            writer.write_all(file.as_bytes()).await?;
-            writer.flush().await?;
-            tx_copy.send(true).await?;
-            Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
-        });
-    }
-    drop(tx);
-
-    while let Some(_) = rx.recv().await {}
-    Ok(())
+        if use_reexport && modules.values().any(|names| !names.is_empty()) {
+            file.push('\n');
+            for (module_name, names) in modules {
+                let mut names = names.clone();
+                names.sort();
+                match names.len() {
+                    0 => {}
+                    1 => file.push_str(&format!(
+                        "pub use {}::{};\n",
+                        get_module_ident(module_name),
+                        names[0]
+                    )),
+                    _ => file.push_str(&format!(
+                        "pub use {}::{{{}}};\n",
+                        get_module_ident(module_name),
+                        names.join(", ")
+                    )),
+                }
+            }
+        }
+        file_list.insert(directory.join("mod.rs"), file);
+    }
+    file_list
+}
+
+/// Module identifier of a file or directory name: ASCII identifier chars, a `_` prefix
+/// before a leading digit and keywords escaped.
+fn get_module_ident(module_name: &str) -> String {
+    let mut module_ident: String = module_name
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() || c == '_' {
+            true => c,
+            false => '_',
+        })
+        .collect();
+    if module_ident.is_empty() || module_ident.starts_with(|c: char| c.is_ascii_digit()) {
+        module_ident.insert(0, '_');
+    }
+    match (
+        is_rust_keyword(&module_ident),
+        NON_RAW_KEYWORDS.contains(&module_ident.as_str()),
+    ) {
+        (true, true) => format!("{}_", module_ident),
+        (true, false) => format!("r#{}", module_ident),
+        (false, _) => module_ident,
+    }
+}
+
+/// Parses generated rust code with `syn` and prints it with `prettyplease`, so
+/// invalid code fails the generation instead of the crate that uses it.
+pub fn format_rust_file(path: &Path, file: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let syntax_tree = syn::parse_file(file)
+        .map_err(|error| format!("invalid rust code generated for {:?}: {}", path, error))?;
+    Ok(prettyplease::unparse(&syntax_tree))
+}
+
+/// Prints items built with `quote!`, a blank line after the last item.
+pub fn unparse_items(tokens: TokenStream) -> String {
+    let syntax_tree: syn::File = syn::parse2(tokens).expect("items built with quote");
+    format!("{}\n", prettyplease::unparse(&syntax_tree))
+}
+
+/// Returns the ident of a generated name, `r#type` as a raw ident.
+pub fn make_ident(name: &str) -> Ident {
+    match name.strip_prefix("r#") {
+        Some(name) => Ident::new_raw(name, Span::call_site()),
+        None => Ident::new(name, Span::call_site()),
+    }
+}
+
+/// Returns a type of `get_rust_type` or `get_nullable_type` as tokens.
+pub fn make_type(type_name: &str) -> syn::Type {
+    syn::parse_str(type_name).expect("rust type")
+}
+
+/// Returns a `///` line per line of `description`, the attributes `make_comment` writes.
+pub fn make_doc_attrs(description: &Option<String>) -> Vec<TokenStream> {
+    let Some(description) = description else {
+        return vec![];
+    };
+    description
+        .lines()
+        .map(|line| {
+            let line = format!(" {}", line).trim_end().to_string();
+            quote! { #[doc = #line] }
+        })
+        .collect()
+}
+
+/// Returns `path`, or `default_name` under the current directory.
+pub fn get_output_path(
+    path: &Option<String>,
+    default_name: &str,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(match path {
+        Some(path) => PathBuf::from_str(path.as_str())?,
+        None => env::current_dir()?.join(default_name),
+    })
+}
+
+/// Returns `path/file_name`, under the split directory of the table when it has one.
+pub fn get_split_file_path(
+    path: &Path,
+    table: &Table,
+    split_directorys: &[SplitDirectoryConfig],
+    file_name: &str,
+) -> PathBuf {
+    match get_split_directory_name(table, split_directorys) {
+        Some(directory_name) => path.join(directory_name).join(file_name),
+        None => path.join(file_name),
+    }
+}
+
+pub fn get_rust_type(data_type: &str) -> &'static str {
+    match data_type {
+        "bit" => "bool",
+        "tinyint" => "u8",
+        "smallint" => "i16",
+        "int" => "i32",
+        "bigint" => "i64",
+        "real" => "f32",
+        "float" => "f64",
+        "money" => "f64",
+        "datetime" => "DateTime<Utc>",
+        "binary" => "Vec<u8>",
+        "image" => "Vec<u8>",
+        "varbinary" => "Vec<u8>",
+        "timestamp" => "Vec<u8>",
+        "rowversion" => "Vec<u8>",
+        "ntext" => "String",
+        "nvarchar" => "String",
+        "text" => "String",
+        _ => "String",
+    }
+}
+
+pub fn get_field_type(column: &ColumnName) -> String {
+    get_nullable_type(column, get_rust_type(column.data_type.as_str()))
+}
+
+/// Wraps `data_type` in `Option` when the column is nullable.
+pub fn get_nullable_type(column: &ColumnName, data_type: &str) -> String {
+    match column.is_nullable.as_str() == "YES" {
+        true => format!("Option<{}>", data_type),
+        false => data_type.into(),
+    }
+}
+
+/// Writes a description line by line after `prefix`, `"    /// "` for a doc comment
+/// of a rust field. Returns nothing without a description.
+pub fn make_comment(description: &Option<String>, prefix: &str) -> String {
+    let mut comment = String::new();
+    if let Some(description) = description {
+        for line in description.lines() {
+            comment.push_str(format!("{}{}", prefix, line).trim_end());
+            comment.push('\n');
+        }
+    }
+    comment
+}
+
+pub fn get_table_names(table: &Table) -> (String, String, String) {
+    let table_name = table.idents.struct_name.clone();
+    let file_name = table.idents.file_name.clone();
+    let sql_table_name = table.name.get_sql_table_name();
+    (table_name, file_name, sql_table_name)
+}
+
+pub fn get_split_directory_name<'a>(
+    table: &Table,
+    split_directorys: &'a [SplitDirectoryConfig],
+) -> Option<&'a str> {
+    let config_name = table.name.get_config_name();
+    split_directorys
+        .iter()
+        .find(|split_directory| config_name.starts_with(&split_directory.starts_with_name))
+        .map(|split_directory| split_directory.directory_name.as_str())
 }
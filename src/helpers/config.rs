@@ -1,2 +1,8 @@
 pub const STRUCT_FILE_NAME: &str = "structs.rs";
 pub const STRUCT_SIGNAL_FILE_NAME: &str = "signals.rs";
+pub const STRUCT_TYPESCRIPT_FILE_NAME: &str = "structs.ts";
+pub const STRUCT_DART_FILE_NAME: &str = "models.dart";
+pub const STRUCT_PROTO_FILE_NAME: &str = "structs.proto";
+pub const PROTO_LOCK_FILE_NAME: &str = "proto.lock";
+pub const STRUCT_CSHARP_FILE_NAME: &str = "Structs.cs";
+pub const STRUCT_PYTHON_FILE_NAME: &str = "models.py";
@@ -0,0 +1,239 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use heck::ToUpperCamelCase;
+
+#[cfg(test)]
+use crate::helpers::fixtures::{make_table, resolve_tables};
+use crate::helpers::{
+    common::{get_output_path, get_split_file_path, get_table_names, make_comment},
+    config::STRUCT_CSHARP_FILE_NAME,
+    structs::{ColumnName, GeneratorOptions, Table},
+    traits::code_generator::CodeGenerator,
+};
+
+#[test]
+fn test_make_csharp_type() {
+    let mut table_list = resolve_tables(
+        vec![make_table(
+            "ritem",
+            &[
+                ("ItemNo", "int", "NO"),
+                ("Item Name", "nvarchar", "YES"),
+                ("Code", "varchar", "NO"),
+                ("Price", "money", "YES"),
+            ],
+        )],
+        false,
+    );
+    table_list[0].primary_keys = vec!["ItemNo".into()];
+
+    let file = make_csharp_type("JkrtdbDboRitem", &table_list[0], true);
+    assert!(
+        file.starts_with("[Table(\"ritem\", Schema = \"dbo\")]\npublic record JkrtdbDboRitem\n{\n")
+    );
+    assert!(file
+        .contains("    [Key]\n    [Column(\"ItemNo\")]\n    public int ItemNo { get; init; }\n"));
+    assert!(
+        file.contains("    [Column(\"Item Name\")]\n    public string? ItemName { get; init; }\n")
+    );
+    assert!(file.contains("    public string Code { get; init; } = default!;\n"));
+    assert!(file.contains("    public decimal? Price { get; init; }\n"));
+
+    table_list[0].primary_keys.push("Code".into());
+    let file = make_csharp_type("JkrtdbDboRitem", &table_list[0], false);
+    assert!(file.starts_with("[Table(\"ritem\", Schema = \"dbo\")]\npublic class JkrtdbDboRitem\n"));
+    assert!(file.contains("    [Key]\n    [Column(\"Code\", Order = 1)]\n    public string Code { get; set; } = default!;\n"));
+}
+
+/// C# records or classes with DataAnnotations attributes of every table.
+pub struct CSharpGenerator;
+
+impl CodeGenerator for CSharpGenerator {
+    fn name(&self) -> &'static str {
+        "csharp"
+    }
+
+    fn generate(
+        &self,
+        table_list: &[Table],
+        options: &GeneratorOptions,
+    ) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+        let header = make_header(&options.csharp_namespace);
+        let mut file_list: HashMap<PathBuf, String> = HashMap::new();
+        match options.use_split_file {
+            true => {
+                let path = get_output_path(&options.csharp_path, "csharp")?;
+                for table in table_list {
+                    let (table_name, _, _) = get_table_names(table);
+                    let current_path = get_split_file_path(
+                        &path,
+                        table,
+                        &options.split_directorys,
+                        &format!("{}.cs", table_name),
+                    );
+                    let file = format!(
+                        "{}{}",
+                        header,
+                        make_csharp_type(&table_name, table, options.use_csharp_record)
+                    );
+                    file_list.insert(current_path, file);
+                }
+            }
+            false => {
+                let path = get_output_path(&options.csharp_path, STRUCT_CSHARP_FILE_NAME)?;
+                let types: Vec<String> = table_list
+                    .iter()
+                    .map(|table| {
+                        let (table_name, _, _) = get_table_names(table);
+                        make_csharp_type(&table_name, table, options.use_csharp_record)
+                    })
+                    .collect();
+                file_list.insert(path, format!("{}{}", header, types.join("\n")));
+            }
+        }
+        Ok(file_list)
+    }
+}
+
+fn make_header(namespace: &Option<String>) -> String {
+    let mut file: String = "#nullable enable\n\nusing System;\nusing System.ComponentModel.DataAnnotations;\nusing System.ComponentModel.DataAnnotations.Schema;\n\n".into();
+    if let Some(namespace) = namespace {
+        file.push_str(&format!("namespace {};\n\n", namespace));
+    }
+    file
+}
+
+fn make_csharp_type(table_name: &str, table: &Table, use_csharp_record: bool) -> String {
+    let (kind, accessor) = match use_csharp_record {
+        true => ("record", "init"),
+        false => ("class", "set"),
+    };
+    let mut columns: Vec<&ColumnName> = table.columns.iter().collect();
+    columns.sort_by_key(|column| column.ordinal_position);
+
+    let mut file = String::new();
+    file.push_str(&make_summary(&table.description, ""));
+    file.push_str(&format!(
+        "[Table({}, Schema = {})]\n",
+        quote_csharp_string(&table.name.table_name),
+        quote_csharp_string(&table.name.table_schema)
+    ));
+    file.push_str(&format!("public {} {}\n{{\n", kind, table_name));
+
+    let mut property_names: HashSet<String> = HashSet::new();
+    let properties: Vec<String> = columns
+        .iter()
+        .map(|column| {
+            let mut property = String::new();
+            property.push_str(&make_summary(&column.description, "    "));
+            let key_index = table
+                .primary_keys
+                .iter()
+                .position(|key| key == &column.column_name);
+            if key_index.is_some() {
+                property.push_str("    [Key]\n");
+            }
+            // EF6 reads the order of composite keys from the column attribute.
+            match key_index.filter(|_| table.primary_keys.len() > 1) {
+                Some(key_index) => property.push_str(&format!(
+                    "    [Column({}, Order = {})]\n",
+                    quote_csharp_string(&column.column_name),
+                    key_index
+                )),
+                None => property.push_str(&format!(
+                    "    [Column({})]\n",
+                    quote_csharp_string(&column.column_name)
+                )),
+            }
+
+            let data_type = get_csharp_type(&column.data_type);
+            let is_reference = matches!(data_type, "string" | "byte[]");
+            let (field_type, initializer) = match (column.is_nullable == "YES", is_reference) {
+                (true, _) => (format!("{}?", data_type), ""),
+                (false, true) => (data_type.to_string(), " = default!;"),
+                (false, false) => (data_type.to_string(), ""),
+            };
+            property.push_str(&format!(
+                "    public {} {} {{ get; {}; }}{}\n",
+                field_type,
+                get_property_name(column, table_name, &mut property_names),
+                accessor,
+                initializer
+            ));
+            property
+        })
+        .collect();
+    file.push_str(&properties.join("\n"));
+    file.push_str("}\n");
+    file
+}
+
+/// XML documentation comment of a type or property.
+fn make_summary(description: &Option<String>, indent: &str) -> String {
+    match description {
+        Some(description) => {
+            let description = description
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            format!(
+                "{0}/// <summary>\n{1}{0}/// </summary>\n",
+                indent,
+                make_comment(&Some(description), &format!("{}/// ", indent))
+            )
+        }
+        None => String::new(),
+    }
+}
+
+/// PascalCase of the resolved field name, unique in the type and not the type name.
+fn get_property_name(
+    column: &ColumnName,
+    table_name: &str,
+    property_names: &mut HashSet<String>,
+) -> String {
+    let mut property_name = column
+        .field_name
+        .trim_start_matches("r#")
+        .to_upper_camel_case();
+    if property_name.is_empty() || property_name.starts_with(|c: char| c.is_ascii_digit()) {
+        property_name = format!("_{}", property_name);
+    }
+    // A member can not have the name of its enclosing type.
+    if property_name == table_name {
+        property_name.push_str("Value");
+    }
+    let mut unique_name = property_name.clone();
+    let mut index = 2;
+    while !property_names.insert(unique_name.clone()) {
+        unique_name = format!("{}{}", property_name, index);
+        index += 1;
+    }
+    unique_name
+}
+
+pub fn get_csharp_type(data_type: &str) -> &'static str {
+    match data_type {
+        "bit" => "bool",
+        "tinyint" => "byte",
+        "smallint" => "short",
+        "int" => "int",
+        "bigint" => "long",
+        "real" => "float",
+        "float" => "double",
+        "decimal" | "numeric" | "money" | "smallmoney" => "decimal",
+        "date" | "datetime" | "datetime2" | "smalldatetime" => "DateTime",
+        "datetimeoffset" => "DateTimeOffset",
+        "time" => "TimeSpan",
+        "uniqueidentifier" => "Guid",
+        "binary" | "varbinary" | "image" | "timestamp" | "rowversion" => "byte[]",
+        _ => "string",
+    }
+}
+
+fn quote_csharp_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
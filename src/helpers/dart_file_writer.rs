@@ -0,0 +1,339 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use heck::ToLowerCamelCase;
+
+#[cfg(test)]
+use crate::helpers::fixtures::{make_table, resolve_tables};
+use crate::helpers::{
+    common::{get_output_path, get_rust_type, get_split_file_path, get_table_names, make_comment},
+    config::STRUCT_DART_FILE_NAME,
+    structs::{ColumnName, GeneratorOptions, SplitDirectoryConfig, Table},
+    traits::code_generator::CodeGenerator,
+};
+
+#[test]
+fn test_make_class() {
+    let table_list = resolve_tables(
+        vec![make_table(
+            "ritem",
+            &[
+                ("ItemNo", "int", "NO"),
+                ("Item Name", "nvarchar", "YES"),
+                ("RegDate", "datetime", "YES"),
+                ("class", "float", "NO"),
+            ],
+        )],
+        false,
+    );
+
+    let file = make_class("JkrtdbDboRitem", &table_list[0], false);
+    assert!(file.starts_with("@JsonSerializable()\nclass JkrtdbDboRitem {\n"));
+    assert!(file.contains("  @JsonKey(name: 'ItemNo')\n  final int itemNo;\n"));
+    assert!(file.contains("  @JsonKey(name: 'Item Name')\n  final String? itemName;\n"));
+    assert!(file.contains("  @JsonKey(name: 'RegDate', fromJson: _nullableDateTimeFromEpochSeconds, toJson: _nullableDateTimeToEpochSeconds)\n  final DateTime? regDate;\n"));
+    assert!(file.contains("  @JsonKey(name: 'class')\n  final double class_;\n"));
+    assert!(file.contains("    required this.itemNo,\n"));
+    assert!(file.contains("  Map<String, dynamic> toJson() => _$JkrtdbDboRitemToJson(this);\n"));
+
+    let file = make_class("JkrtdbDboRitem", &table_list[0], true);
+    assert!(file.starts_with("@freezed\nabstract class JkrtdbDboRitem with _$JkrtdbDboRitem {\n"));
+    assert!(file.contains("    @JsonKey(name: 'Item Name') required String? itemName,\n"));
+    assert!(file.contains("  }) = _JkrtdbDboRitem;\n"));
+
+    let converters = import_date_time_converters(&file);
+    assert!(converters.contains("DateTime.fromMillisecondsSinceEpoch(value * 1000, isUtc: true)"));
+    assert!(converters.contains("value.millisecondsSinceEpoch ~/ 1000"));
+    assert!(converters.contains("DateTime? _nullableDateTimeFromEpochSeconds(int? value) =>"));
+    assert!(import_date_time_converters("class Empty {}\n").is_empty());
+
+    assert_eq!(
+        make_header(Path::new("lib/models.dart"), true),
+        "import 'package:freezed_annotation/freezed_annotation.dart';\n\npart 'models.freezed.dart';\npart 'models.g.dart';\n\n"
+    );
+}
+
+/// json_serializable or freezed classes of the tables with `use_dart_file`.
+pub struct DartGenerator;
+
+impl CodeGenerator for DartGenerator {
+    fn name(&self) -> &'static str {
+        "dart"
+    }
+
+    fn generate(
+        &self,
+        table_list: &[Table],
+        options: &GeneratorOptions,
+    ) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+        match options.use_split_file {
+            true => make_dart_split_files(
+                &options.dart_path,
+                options.use_freezed,
+                table_list,
+                &options.split_directorys,
+            ),
+            false => make_dart_one_file(&options.dart_path, options.use_freezed, table_list),
+        }
+    }
+}
+
+fn make_dart_one_file(
+    path: &Option<String>,
+    use_freezed: bool,
+    table_list: &[Table],
+) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+    let path = get_output_path(path, STRUCT_DART_FILE_NAME)?;
+    let classes: Vec<String> = table_list
+        .iter()
+        .filter(|table| table.use_dart_file)
+        .map(|table| {
+            let (table_name, _, _) = get_table_names(table);
+            make_class(&table_name, table, use_freezed)
+        })
+        .collect();
+    let mut file = format!("{}{}", make_header(&path, use_freezed), classes.join("\n"));
+    file.push_str(&import_date_time_converters(&file));
+    Ok(HashMap::from([(path, file)]))
+}
+
+fn make_dart_split_files(
+    path: &Option<String>,
+    use_freezed: bool,
+    table_list: &[Table],
+    split_directorys: &[SplitDirectoryConfig],
+) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+    let path = get_output_path(path, "models")?;
+    let mut file_list: HashMap<PathBuf, String> = HashMap::new();
+
+    for table in table_list {
+        if !table.use_dart_file {
+            continue;
+        }
+        let (table_name, file_name, _) = get_table_names(table);
+        let current_path = get_split_file_path(
+            &path,
+            table,
+            split_directorys,
+            &format!("{}.dart", file_name),
+        );
+        let mut file = format!(
+            "{}{}",
+            make_header(&current_path, use_freezed),
+            make_class(&table_name, table, use_freezed)
+        );
+        file.push_str(&import_date_time_converters(&file));
+        file_list.insert(current_path, file);
+    }
+    let file_paths: Vec<PathBuf> = file_list.keys().cloned().collect();
+    file_list.extend(make_index_files(&path, &file_paths));
+    Ok(file_list)
+}
+
+/// Returns `index.dart` of `root` and every directory between it and the files, with
+/// `export` of each file and each child `index.dart`.
+fn make_index_files(root: &Path, file_paths: &[PathBuf]) -> HashMap<PathBuf, String> {
+    let mut tree: BTreeMap<PathBuf, BTreeSet<String>> = BTreeMap::new();
+    tree.entry(root.to_path_buf()).or_default();
+    for file_path in file_paths {
+        let mut path = file_path.clone();
+        while let Some(parent) = path.parent() {
+            if !parent.starts_with(root) {
+                break;
+            }
+            let export_name = match path == *file_path {
+                true => path.file_name().unwrap().to_string_lossy().to_string(),
+                false => format!("{}/index.dart", path.file_name().unwrap().to_string_lossy()),
+            };
+            tree.entry(parent.to_path_buf())
+                .or_default()
+                .insert(export_name);
+            path = parent.to_path_buf();
+        }
+    }
+
+    tree.into_iter()
+        .map(|(directory, exports)| {
+            let file: String = exports
+                .iter()
+                .map(|export_name| format!("export '{}';\n", export_name))
+                .collect();
+            (directory.join("index.dart"), file)
+        })
+        .collect()
+}
+
+/// Imports and the `part` files build_runner writes next to `path`.
+fn make_header(path: &Path, use_freezed: bool) -> String {
+    let file_stem = path
+        .file_stem()
+        .map(|file_stem| file_stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match use_freezed {
+        true => format!(
+            "import 'package:freezed_annotation/freezed_annotation.dart';\n\npart '{0}.freezed.dart';\npart '{0}.g.dart';\n\n",
+            file_stem
+        ),
+        false => format!(
+            "import 'package:json_annotation/json_annotation.dart';\n\npart '{}.g.dart';\n\n",
+            file_stem
+        ),
+    }
+}
+
+fn make_class(table_name: &str, table: &Table, use_freezed: bool) -> String {
+    let mut field_names: HashSet<String> = HashSet::new();
+    let fields: Vec<(String, String, &ColumnName)> = table
+        .columns
+        .iter()
+        .map(|column| {
+            let field_name = get_dart_field_name(column, &mut field_names);
+            (field_name, get_dart_field_type(column), column)
+        })
+        .collect();
+
+    let mut file = String::new();
+    file.push_str(&make_comment(&table.description, "/// "));
+    match use_freezed {
+        true => {
+            file.push_str("@freezed\n");
+            file.push_str(&format!(
+                "abstract class {0} with _${0} {{\n  const factory {0}({{\n",
+                table_name
+            ));
+            for (field_name, field_type, column) in &fields {
+                file.push_str(&make_comment(&column.description, "    /// "));
+                file.push_str(&format!(
+                    "    {} required {} {},\n",
+                    get_json_key(column),
+                    field_type,
+                    field_name
+                ));
+            }
+            file.push_str(&format!("  }}) = _{};\n\n", table_name));
+        }
+        false => {
+            file.push_str("@JsonSerializable()\n");
+            file.push_str(&format!("class {} {{\n", table_name));
+            for (field_name, field_type, column) in &fields {
+                file.push_str(&make_comment(&column.description, "  /// "));
+                file.push_str(&format!(
+                    "  {}\n  final {} {};\n",
+                    get_json_key(column),
+                    field_type,
+                    field_name
+                ));
+            }
+            file.push_str(&format!("\n  const {}({{\n", table_name));
+            for (field_name, _, _) in &fields {
+                file.push_str(&format!("    required this.{},\n", field_name));
+            }
+            file.push_str("  });\n\n");
+        }
+    }
+    file.push_str(&format!(
+        "  factory {0}.fromJson(Map<String, dynamic> json) => _${0}FromJson(json);\n",
+        table_name
+    ));
+    if !use_freezed {
+        file.push_str(&format!(
+            "\n  Map<String, dynamic> toJson() => _${}ToJson(this);\n",
+            table_name
+        ));
+    }
+    file.push_str("}\n");
+    file
+}
+
+const DART_RESERVED_WORDS: [&str; 33] = [
+    "assert", "break", "case", "catch", "class", "const", "continue", "default", "do", "else",
+    "enum", "extends", "false", "final", "finally", "for", "if", "in", "is", "new", "null",
+    "rethrow", "return", "super", "switch", "this", "throw", "true", "try", "var", "void", "while",
+    "with",
+];
+
+/// lowerCamelCase of the resolved field name, unique in the class. The json key stays
+/// the original column name with `@JsonKey`.
+fn get_dart_field_name(column: &ColumnName, field_names: &mut HashSet<String>) -> String {
+    let mut field_name = column
+        .field_name
+        .trim_start_matches("r#")
+        .to_lower_camel_case();
+    if field_name.is_empty() || field_name.starts_with(|c: char| c.is_ascii_digit()) {
+        field_name = format!("field{}", field_name);
+    }
+    if DART_RESERVED_WORDS.contains(&field_name.as_str()) {
+        field_name.push('_');
+    }
+    let mut unique_name = field_name.clone();
+    let mut index = 2;
+    while !field_names.insert(unique_name.clone()) {
+        unique_name = format!("{}{}", field_name, index);
+        index += 1;
+    }
+    unique_name
+}
+
+/// Dart type of the field, datetime is read from the epoch seconds of `ts_seconds`.
+pub fn get_dart_type(data_type: &str) -> &'static str {
+    match get_rust_type(data_type) {
+        "bool" => "bool",
+        "u8" | "i16" | "i32" | "i64" => "int",
+        "f32" | "f64" => "double",
+        "DateTime<Utc>" => "DateTime",
+        "Vec<u8>" => "List<int>",
+        _ => "String",
+    }
+}
+
+fn get_dart_field_type(column: &ColumnName) -> String {
+    let data_type = get_dart_type(&column.data_type);
+    match column.is_nullable.as_str() == "YES" {
+        true => format!("{}?", data_type),
+        false => data_type.into(),
+    }
+}
+
+/// `@JsonKey` with the original column name, and the epoch seconds converters of datetime.
+fn get_json_key(column: &ColumnName) -> String {
+    let name = quote_dart_string(&column.column_name);
+    match (
+        get_rust_type(&column.data_type),
+        column.is_nullable.as_str() == "YES",
+    ) {
+        ("DateTime<Utc>", false) => format!(
+            "@JsonKey(name: {}, fromJson: _dateTimeFromEpochSeconds, toJson: _dateTimeToEpochSeconds)",
+            name
+        ),
+        ("DateTime<Utc>", true) => format!(
+            "@JsonKey(name: {}, fromJson: _nullableDateTimeFromEpochSeconds, toJson: _nullableDateTimeToEpochSeconds)",
+            name
+        ),
+        _ => format!("@JsonKey(name: {})", name),
+    }
+}
+
+/// Returns the converters the `@JsonKey` of the file use, private to its library.
+fn import_date_time_converters(file: &str) -> String {
+    let mut import_file = String::new();
+    if file.contains("DateTimeFromEpochSeconds,") {
+        import_file.push_str("\nDateTime _dateTimeFromEpochSeconds(int value) =>\n    DateTime.fromMillisecondsSinceEpoch(value * 1000, isUtc: true);\n");
+        import_file.push_str("\nint _dateTimeToEpochSeconds(DateTime value) => value.millisecondsSinceEpoch ~/ 1000;\n");
+    }
+    if file.contains("_nullableDateTimeFromEpochSeconds,") {
+        import_file.push_str("\nDateTime? _nullableDateTimeFromEpochSeconds(int? value) =>\n    value == null ? null : _dateTimeFromEpochSeconds(value);\n");
+        import_file.push_str("\nint? _nullableDateTimeToEpochSeconds(DateTime? value) =>\n    value == null ? null : _dateTimeToEpochSeconds(value);\n");
+    }
+    import_file
+}
+
+fn quote_dart_string(text: &str) -> String {
+    format!(
+        "'{}'",
+        text.replace('\\', "\\\\")
+            .replace('\'', "\\'")
+            .replace('$', "\\$")
+    )
+}
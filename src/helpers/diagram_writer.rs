@@ -0,0 +1,434 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
+
+#[cfg(test)]
+use crate::helpers::fixtures::{make_table, resolve_tables};
+use crate::helpers::{
+    args_parser::{DiagramFormat, DiagramGroup},
+    common::{get_output_path, get_split_directory_name},
+    structs::{diagram_formats_default, ColumnName, ForeignKey, GeneratorOptions, Table},
+    traits::code_generator::CodeGenerator,
+};
+
+#[test]
+fn test_make_diagrams() {
+    let mut table_list = resolve_tables(
+        vec![
+            make_table(
+                "rcategory",
+                &[("CategoryNo", "int", "NO"), ("Name", "int", "YES")],
+            ),
+            make_table(
+                "ritem",
+                &[
+                    ("ItemNo", "int", "NO"),
+                    ("Category No", "int", "YES"),
+                    ("Price", "int", "NO"),
+                ],
+            ),
+        ],
+        false,
+    );
+    table_list[0].primary_keys = vec!["CategoryNo".into()];
+    table_list[1].primary_keys = vec!["ItemNo".into()];
+    table_list[1].foreign_keys = vec![ForeignKey {
+        name: "FK ritem:rcategory".into(),
+        columns: vec!["Category No".into()],
+        referenced_table_schema: "dbo".into(),
+        referenced_table_name: "rcategory".into(),
+        referenced_columns: vec!["CategoryNo".into()],
+    }];
+
+    let options = GeneratorOptions {
+        diagram_path: Some("diagrams".into()),
+        diagram_formats: vec![
+            DiagramFormat::Mermaid,
+            DiagramFormat::Dot,
+            DiagramFormat::Plantuml,
+        ],
+        diagram_keys_only: true,
+        ..Default::default()
+    };
+    let file_list = DiagramGenerator.generate(&table_list, &options).unwrap();
+    let file = &file_list[&PathBuf::from("diagrams/jkrtdb.mmd")];
+    assert!(
+        file.starts_with("erDiagram\n    JkrtdbDboRcategory {\n        int CategoryNo PK\n    }\n")
+    );
+    assert!(file.contains("        int Category_No FK \"Category No\"\n"));
+    assert!(!file.contains("Price"));
+    assert!(
+        file.contains("    JkrtdbDboRcategory |o--o{ JkrtdbDboRitem : \"FK ritem:rcategory\"\n")
+    );
+    assert!(file_list[&PathBuf::from("diagrams/jkrtdb.dot")].contains(
+        "    JkrtdbDboRitem -> JkrtdbDboRcategory [label=\"FK ritem:rcategory\", style=dashed];\n"
+    ));
+    let file = &file_list[&PathBuf::from("diagrams/jkrtdb.puml")];
+    assert!(file.starts_with("@startuml jkrtdb\n"));
+    assert!(file.contains(
+        "entity \"dbo.ritem\" as JkrtdbDboRitem {\n  * ItemNo : int <<PK>>\n  --\n  \"Category No\" : int <<FK>>\n}\n"
+    ));
+    assert!(file.contains("JkrtdbDboRcategory |o--o{ JkrtdbDboRitem : \"FK ritem:rcategory\"\n"));
+
+    // Tables out of the subset and relationships to them are left out.
+    let options = GeneratorOptions {
+        diagram_tables: Some(vec!["jkrtdb_dbo_ritem".into()]),
+        ..options
+    };
+    let file_list = DiagramGenerator.generate(&table_list, &options).unwrap();
+    let file = &file_list[&PathBuf::from("diagrams/jkrtdb.mmd")];
+    assert!(!file.contains("JkrtdbDboRcategory"));
+
+    // Names match exactly unless they end with *.
+    for (name, expected) in [
+        ("jkrtdb_dbo_r", false),
+        ("jkrtdb_dbo_r*", true),
+        ("JKRTDB_DBO_RITEM", true),
+    ] {
+        assert_eq!(
+            is_diagram_table(&table_list[1], &[name.to_string()]),
+            expected
+        );
+    }
+}
+
+/// `diagram_tables` are config names, a trailing `*` matches the names starting with it.
+fn is_diagram_table(table: &Table, diagram_tables: &[String]) -> bool {
+    let config_name = table.name.get_config_name();
+    diagram_tables.iter().any(|name| {
+        let name = name.to_lowercase();
+        match name.strip_suffix('*') {
+            Some(prefix) => config_name.starts_with(prefix),
+            None => config_name == name,
+        }
+    })
+}
+
+/// ER diagrams of the tables and their foreign keys, per database or split directory.
+pub struct DiagramGenerator;
+
+impl CodeGenerator for DiagramGenerator {
+    fn name(&self) -> &'static str {
+        "diagram"
+    }
+
+    fn generate(
+        &self,
+        table_list: &[Table],
+        options: &GeneratorOptions,
+    ) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+        let path = get_output_path(&options.diagram_path, "diagrams")?;
+        let mut groups: BTreeMap<String, Vec<&Table>> = BTreeMap::new();
+        for table in table_list {
+            if let Some(diagram_tables) = &options.diagram_tables {
+                if !is_diagram_table(table, diagram_tables) {
+                    continue;
+                }
+            }
+            let group_name = match options.diagram_group {
+                DiagramGroup::Database => table.name.table_catalog.clone(),
+                DiagramGroup::SplitDirectory => {
+                    match get_split_directory_name(table, &options.split_directorys) {
+                        Some(directory_name) => directory_name.to_string(),
+                        None => table.name.table_catalog.clone(),
+                    }
+                }
+            };
+            groups.entry(group_name).or_default().push(table);
+        }
+
+        // GeneratorOptions::default has no format, the library gets the cli default.
+        let formats = match options.diagram_formats.is_empty() {
+            true => diagram_formats_default(),
+            false => options.diagram_formats.clone(),
+        };
+        let mut file_list: HashMap<PathBuf, String> = HashMap::new();
+        for (group_name, tables) in &groups {
+            let relationships = get_relationships(tables);
+            for format in &formats {
+                let (extension, file) = match format {
+                    DiagramFormat::Mermaid => (
+                        "mmd",
+                        make_mermaid(tables, &relationships, options.diagram_keys_only),
+                    ),
+                    DiagramFormat::Dot => (
+                        "dot",
+                        make_dot(
+                            group_name,
+                            tables,
+                            &relationships,
+                            options.diagram_keys_only,
+                        ),
+                    ),
+                    DiagramFormat::Plantuml => (
+                        "puml",
+                        make_plantuml(
+                            group_name,
+                            tables,
+                            &relationships,
+                            options.diagram_keys_only,
+                        ),
+                    ),
+                };
+                file_list.insert(path.join(format!("{}.{}", group_name, extension)), file);
+            }
+        }
+        Ok(file_list)
+    }
+}
+
+/// A foreign key whose referenced table is in the same diagram.
+struct Relationship<'a> {
+    table: &'a Table,
+    referenced_table: &'a Table,
+    foreign_key: &'a ForeignKey,
+    /// Every column of the key is nullable, so the reference is optional.
+    is_optional: bool,
+}
+
+fn get_relationships<'a>(tables: &[&'a Table]) -> Vec<Relationship<'a>> {
+    let mut relationships = vec![];
+    for table in tables {
+        for foreign_key in &table.foreign_keys {
+            let Some(referenced_table) = tables.iter().find(|referenced_table| {
+                referenced_table.name.table_catalog == table.name.table_catalog
+                    && referenced_table.name.table_schema == foreign_key.referenced_table_schema
+                    && referenced_table.name.table_name == foreign_key.referenced_table_name
+            }) else {
+                continue;
+            };
+            let is_optional = foreign_key.columns.iter().all(|column_name| {
+                table
+                    .columns
+                    .iter()
+                    .any(|column| &column.column_name == column_name && column.is_nullable == "YES")
+            });
+            relationships.push(Relationship {
+                table,
+                referenced_table,
+                foreign_key,
+                is_optional,
+            });
+        }
+    }
+    relationships
+}
+
+/// Columns in ordinal order with their `PK`/`FK` marks, only the key columns when
+/// `keys_only`.
+fn get_diagram_columns(table: &Table, keys_only: bool) -> Vec<(&ColumnName, Vec<&'static str>)> {
+    let mut columns: Vec<&ColumnName> = table.columns.iter().collect();
+    columns.sort_by_key(|column| column.ordinal_position);
+    columns
+        .into_iter()
+        .map(|column| {
+            let mut keys = vec![];
+            if table.primary_keys.contains(&column.column_name) {
+                keys.push("PK");
+            }
+            if table
+                .foreign_keys
+                .iter()
+                .any(|foreign_key| foreign_key.columns.contains(&column.column_name))
+            {
+                keys.push("FK");
+            }
+            (column, keys)
+        })
+        .filter(|(_, keys)| !keys_only || !keys.is_empty())
+        .collect()
+}
+
+fn get_table_label(table: &Table) -> String {
+    format!("{}.{}", table.name.table_schema, table.name.table_name)
+}
+
+fn make_mermaid(tables: &[&Table], relationships: &[Relationship], keys_only: bool) -> String {
+    // Mermaid names are words, the original name is kept as the comment.
+    let to_word = |text: &str| -> String {
+        let mut word: String = text
+            .chars()
+            .map(|c| match c.is_ascii_alphanumeric() || c == '_' {
+                true => c,
+                false => '_',
+            })
+            .collect();
+        if word.is_empty() || word.starts_with(|c: char| c.is_ascii_digit()) {
+            word.insert(0, '_');
+        }
+        word
+    };
+
+    let mut file: String = "erDiagram\n".into();
+    for table in tables {
+        file.push_str(&format!("    {} {{\n", table.idents.struct_name));
+        for (column, keys) in get_diagram_columns(table, keys_only) {
+            let name = to_word(&column.column_name);
+            file.push_str(&format!("        {} {}", to_word(&column.data_type), name));
+            if !keys.is_empty() {
+                file.push_str(&format!(" {}", keys.join(", ")));
+            }
+            if name != column.column_name {
+                file.push_str(&format!(" \"{}\"", column.column_name.replace('"', "'")));
+            }
+            file.push('\n');
+        }
+        file.push_str("    }\n");
+    }
+    for relationship in relationships {
+        file.push_str(&format!(
+            "    {} {}--o{{ {} : \"{}\"\n",
+            relationship.referenced_table.idents.struct_name,
+            match relationship.is_optional {
+                true => "|o",
+                false => "||",
+            },
+            relationship.table.idents.struct_name,
+            relationship.foreign_key.name.replace('"', "'")
+        ));
+    }
+    file
+}
+
+fn make_dot(
+    group_name: &str,
+    tables: &[&Table],
+    relationships: &[Relationship],
+    keys_only: bool,
+) -> String {
+    let escape = |text: &str| -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    };
+
+    let mut file = format!(
+        "digraph \"{}\" {{\n    graph [rankdir=LR];\n    node [shape=plaintext];\n",
+        group_name.replace('"', "\\\"")
+    );
+    for table in tables {
+        file.push_str(&format!(
+            "    {} [label=<<table border=\"0\" cellborder=\"1\" cellspacing=\"0\"><tr><td bgcolor=\"#eeeeee\"><b>{}</b></td></tr>",
+            table.idents.struct_name,
+            escape(&get_table_label(table))
+        ));
+        for (column, keys) in get_diagram_columns(table, keys_only) {
+            let mut text = format!("{} : {}", column.column_name, column.data_type);
+            if !keys.is_empty() {
+                text.push_str(&format!(" ({})", keys.join(", ")));
+            }
+            file.push_str(&format!(
+                "<tr><td align=\"left\">{}</td></tr>",
+                escape(&text)
+            ));
+        }
+        file.push_str("</table>>];\n");
+    }
+    for relationship in relationships {
+        file.push_str(&format!(
+            "    {} -> {} [label=\"{}\"{}];\n",
+            relationship.table.idents.struct_name,
+            relationship.referenced_table.idents.struct_name,
+            relationship.foreign_key.name.replace('"', "\\\""),
+            match relationship.is_optional {
+                true => ", style=dashed",
+                false => "",
+            }
+        ));
+    }
+    file.push_str("}\n");
+    file
+}
+
+fn make_plantuml(
+    group_name: &str,
+    tables: &[&Table],
+    relationships: &[Relationship],
+    keys_only: bool,
+) -> String {
+    // The @startuml name is the output file name, so it is a word like the mermaid names.
+    let diagram_name: String = group_name
+        .chars()
+        .map(
+            |c| match c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                true => c,
+                false => '_',
+            },
+        )
+        .collect();
+    let mut file = format!(
+        "@startuml {}\nhide circle\nskinparam linetype ortho\n",
+        diagram_name
+    );
+    for table in tables {
+        file.push_str(&format!(
+            "\nentity {} as {} {{\n",
+            quote_plantuml(&get_table_label(table)),
+            table.idents.struct_name
+        ));
+        let columns = get_diagram_columns(table, keys_only);
+        let (primary_columns, other_columns): (Vec<_>, Vec<_>) =
+            columns.iter().partition(|(_, keys)| keys.contains(&"PK"));
+        for (column, keys) in &primary_columns {
+            file.push_str(&make_plantuml_column(column, keys));
+        }
+        if !primary_columns.is_empty() && !other_columns.is_empty() {
+            file.push_str("  --\n");
+        }
+        for (column, keys) in &other_columns {
+            file.push_str(&make_plantuml_column(column, keys));
+        }
+        file.push_str("}\n");
+    }
+    if !relationships.is_empty() {
+        file.push('\n');
+    }
+    for relationship in relationships {
+        file.push_str(&format!(
+            "{} {}--o{{ {} : {}\n",
+            relationship.referenced_table.idents.struct_name,
+            match relationship.is_optional {
+                true => "|o",
+                false => "||",
+            },
+            relationship.table.idents.struct_name,
+            quote_plantuml(&relationship.foreign_key.name)
+        ));
+    }
+    file.push_str("@enduml\n");
+    file
+}
+
+/// Double quoted PlantUML text, quotes become `'` and backslashes are not escape sequences.
+fn quote_plantuml(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "'"))
+}
+
+/// `*` marks a column that is not nullable. Names that are not words are quoted, so
+/// spaces, colons or a leading `-`/`{` are not read as PlantUML syntax.
+fn make_plantuml_column(column: &ColumnName, keys: &[&str]) -> String {
+    let column_name = match column
+        .column_name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_')
+    {
+        true => column.column_name.clone(),
+        false => quote_plantuml(&column.column_name),
+    };
+    let mut line = format!(
+        "  {}{} : {}",
+        match column.is_nullable == "YES" {
+            true => "",
+            false => "* ",
+        },
+        column_name,
+        column.data_type
+    );
+    for key in keys {
+        line.push_str(&format!(" <<{}>>", key));
+    }
+    line.push('\n');
+    line
+}
@@ -0,0 +1,356 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+};
+
+use crate::helpers::{
+    args_parser::DocsFormat,
+    common::get_output_path,
+    structs::{ColumnName, GeneratorOptions, Table},
+    traits::code_generator::CodeGenerator,
+};
+#[cfg(test)]
+use crate::helpers::{
+    fixtures::{make_table, resolve_tables},
+    structs::ForeignKey,
+};
+
+#[test]
+fn test_make_docs() {
+    let mut table_list = resolve_tables(
+        vec![make_table(
+            "ritem",
+            &[
+                ("ItemNo", "int", "NO"),
+                ("Item Name", "nvarchar", "YES"),
+                ("Price", "decimal", "NO"),
+            ],
+        )],
+        false,
+    );
+    let table = &mut table_list[0];
+    table.primary_keys = vec!["ItemNo".into()];
+    table.foreign_keys = vec![ForeignKey {
+        name: "FK_ritem_rprice".into(),
+        columns: vec!["Price".into()],
+        referenced_table_schema: "dbo".into(),
+        referenced_table_name: "rprice".into(),
+        referenced_columns: vec!["Price".into()],
+    }];
+    table.description = Some("Menu items | prices".into());
+    table.columns[0].is_identity = true;
+    table.columns[1].character_maximum_length = Some(-1);
+    table.columns[1].description = Some("<no name>".into());
+    table.columns[2].column_default = Some("((0))".into());
+    table.columns[2].numeric_precision = Some(10);
+    table.columns[2].numeric_scale = Some(2);
+
+    let file_list = DocsGenerator
+        .generate(
+            &table_list,
+            &GeneratorOptions {
+                docs_path: Some("docs".into()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let page = &file_list[&PathBuf::from("docs/jkrtdb.md")];
+    assert!(page.contains("## dbo.ritem\n\nMenu items \\| prices\n\n"));
+    assert!(page.contains("| 1 | ItemNo | int | NO |  | PK, identity |  |\n"));
+    assert!(page.contains("| 2 | Item Name | nvarchar(max) | YES |  |  | &lt;no name> |\n"));
+    assert!(page.contains("| 3 | Price | decimal(10,2) | NO | ((0)) | FK dbo.rprice.Price |  |\n"));
+    assert_eq!(
+        file_list[&PathBuf::from("docs/index.md")],
+        "# Data dictionary\n\n| Database | Tables |\n| --- | --- |\n| [jkrtdb](jkrtdb.md) | 1 |\n"
+    );
+
+    let file_list = DocsGenerator
+        .generate(
+            &table_list,
+            &GeneratorOptions {
+                docs_path: Some("docs".into()),
+                docs_format: DocsFormat::Html,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let page = &file_list[&PathBuf::from("docs/jkrtdb.html")];
+    assert!(page.contains("<td>Item Name</td><td>nvarchar(max)</td><td>YES</td><td></td><td></td><td>&lt;no name&gt;</td>"));
+    assert!(
+        file_list[&PathBuf::from("docs/index.html")].contains("<a href=\"jkrtdb.html\">jkrtdb</a>")
+    );
+}
+
+/// Data dictionary with a page per database and an index page.
+pub struct DocsGenerator;
+
+impl CodeGenerator for DocsGenerator {
+    fn name(&self) -> &'static str {
+        "docs"
+    }
+
+    fn generate(
+        &self,
+        table_list: &[Table],
+        options: &GeneratorOptions,
+    ) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+        let path = get_output_path(&options.docs_path, "docs")?;
+        let mut databases: BTreeMap<&str, Vec<&Table>> = BTreeMap::new();
+        for table in table_list {
+            databases
+                .entry(table.name.table_catalog.as_str())
+                .or_default()
+                .push(table);
+        }
+
+        let extension = match options.docs_format {
+            DocsFormat::Markdown => "md",
+            DocsFormat::Html => "html",
+        };
+        let mut file_list: HashMap<PathBuf, String> = HashMap::new();
+        for (database_name, tables) in &databases {
+            let file = match options.docs_format {
+                DocsFormat::Markdown => make_markdown_page(database_name, tables),
+                DocsFormat::Html => make_html_page(database_name, tables),
+            };
+            file_list.insert(path.join(format!("{}.{}", database_name, extension)), file);
+        }
+        let index = match options.docs_format {
+            DocsFormat::Markdown => make_markdown_index(&databases),
+            DocsFormat::Html => make_html_index(&databases),
+        };
+        file_list.insert(path.join(format!("index.{}", extension)), index);
+        Ok(file_list)
+    }
+}
+
+/// Cells of a column row: position, name, type, nullable, default, keys and description.
+fn get_column_cells(table: &Table, column: &ColumnName) -> [String; 7] {
+    let mut keys: Vec<&str> = vec![];
+    if table.primary_keys.contains(&column.column_name) {
+        keys.push("PK");
+    }
+    if column.is_identity {
+        keys.push("identity");
+    }
+    if column.is_computed {
+        keys.push("computed");
+    }
+    let mut keys: Vec<String> = keys.into_iter().map(|key| key.to_string()).collect();
+    for foreign_key in &table.foreign_keys {
+        if let Some(index) = foreign_key
+            .columns
+            .iter()
+            .position(|column_name| column_name == &column.column_name)
+        {
+            keys.push(format!(
+                "FK {}.{}.{}",
+                foreign_key.referenced_table_schema,
+                foreign_key.referenced_table_name,
+                foreign_key.referenced_columns[index]
+            ));
+        }
+    }
+    [
+        column.ordinal_position.to_string(),
+        column.column_name.clone(),
+        get_sql_type(column),
+        column.is_nullable.clone(),
+        column.column_default.clone().unwrap_or_default(),
+        keys.join(", "),
+        column.description.clone().unwrap_or_default(),
+    ]
+}
+
+const COLUMN_HEADERS: [&str; 7] = [
+    "#",
+    "Column",
+    "Type",
+    "Nullable",
+    "Default",
+    "Key",
+    "Description",
+];
+
+fn get_sorted_columns(table: &Table) -> Vec<&ColumnName> {
+    let mut columns: Vec<&ColumnName> = table.columns.iter().collect();
+    columns.sort_by_key(|column| column.ordinal_position);
+    columns
+}
+
+fn make_markdown_page(database_name: &str, tables: &[&Table]) -> String {
+    let mut file = format!("# {}\n\n", escape_markdown(database_name));
+    for table in tables {
+        let name = format!("{}.{}", table.name.table_schema, table.name.table_name);
+        file.push_str(&format!(
+            "- [{}](#{})\n",
+            escape_markdown(&name),
+            get_anchor(&name)
+        ));
+    }
+
+    for table in tables {
+        let name = format!("{}.{}", table.name.table_schema, table.name.table_name);
+        file.push_str(&format!("\n## {}\n\n", escape_markdown(&name)));
+        if let Some(description) = &table.description {
+            file.push_str(&format!("{}\n\n", escape_markdown(description)));
+        }
+        file.push_str(&format!(
+            "Type: {}, struct: `{}`",
+            table.name.table_type, table.idents.struct_name
+        ));
+        if !table.primary_keys.is_empty() {
+            file.push_str(&format!(
+                ", primary key: {}",
+                escape_markdown(&table.primary_keys.join(", "))
+            ));
+        }
+        file.push_str("\n\n");
+
+        file.push_str(&format!("| {} |\n", COLUMN_HEADERS.join(" | ")));
+        file.push_str(&format!("|{}\n", " --- |".repeat(COLUMN_HEADERS.len())));
+        for column in get_sorted_columns(table) {
+            let cells: Vec<String> = get_column_cells(table, column)
+                .iter()
+                .map(|cell| escape_markdown(cell))
+                .collect();
+            file.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+    }
+    file
+}
+
+fn make_markdown_index(databases: &BTreeMap<&str, Vec<&Table>>) -> String {
+    let mut file: String = "# Data dictionary\n\n| Database | Tables |\n| --- | --- |\n".into();
+    for (database_name, tables) in databases {
+        file.push_str(&format!(
+            "| [{0}]({0}.md) | {1} |\n",
+            escape_markdown(database_name),
+            tables.len()
+        ));
+    }
+    file
+}
+
+fn make_html_page(database_name: &str, tables: &[&Table]) -> String {
+    let mut body = format!(
+        "<p><a href=\"index.html\">Data dictionary</a></p>\n<h1>{}</h1>\n<ul>\n",
+        escape_html(database_name)
+    );
+    for table in tables {
+        let name = format!("{}.{}", table.name.table_schema, table.name.table_name);
+        body.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a></li>\n",
+            escape_html(&get_anchor(&name)),
+            escape_html(&name)
+        ));
+    }
+    body.push_str("</ul>\n");
+
+    for table in tables {
+        let name = format!("{}.{}", table.name.table_schema, table.name.table_name);
+        body.push_str(&format!(
+            "<h2 id=\"{}\">{}</h2>\n",
+            escape_html(&get_anchor(&name)),
+            escape_html(&name)
+        ));
+        if let Some(description) = &table.description {
+            body.push_str(&format!("<p>{}</p>\n", escape_html(description)));
+        }
+        body.push_str(&format!(
+            "<p>Type: {}, struct: <code>{}</code>",
+            escape_html(&table.name.table_type),
+            escape_html(&table.idents.struct_name)
+        ));
+        if !table.primary_keys.is_empty() {
+            body.push_str(&format!(
+                ", primary key: {}",
+                escape_html(&table.primary_keys.join(", "))
+            ));
+        }
+        body.push_str("</p>\n<table>\n<tr>");
+        for header in COLUMN_HEADERS {
+            body.push_str(&format!("<th>{}</th>", escape_html(header)));
+        }
+        body.push_str("</tr>\n");
+        for column in get_sorted_columns(table) {
+            body.push_str("<tr>");
+            for cell in get_column_cells(table, column) {
+                body.push_str(&format!("<td>{}</td>", escape_html(&cell)));
+            }
+            body.push_str("</tr>\n");
+        }
+        body.push_str("</table>\n");
+    }
+    make_html_document(database_name, &body)
+}
+
+fn make_html_index(databases: &BTreeMap<&str, Vec<&Table>>) -> String {
+    let mut body: String =
+        "<h1>Data dictionary</h1>\n<table>\n<tr><th>Database</th><th>Tables</th></tr>\n".into();
+    for (database_name, tables) in databases {
+        body.push_str(&format!(
+            "<tr><td><a href=\"{0}.html\">{0}</a></td><td>{1}</td></tr>\n",
+            escape_html(database_name),
+            tables.len()
+        ));
+    }
+    body.push_str("</table>\n");
+    make_html_document("Data dictionary", &body)
+}
+
+fn make_html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\nbody {{ font-family: sans-serif; margin: 2em; }}\ntable {{ border-collapse: collapse; margin-bottom: 2em; }}\nth, td {{ border: 1px solid #ccc; padding: 4px 8px; text-align: left; }}\nth {{ background: #f4f4f4; }}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        body
+    )
+}
+
+/// SQL type with its length or precision, `nvarchar(50)`, `nvarchar(max)`, `decimal(10,2)`.
+pub fn get_sql_type(column: &ColumnName) -> String {
+    match column.data_type.as_str() {
+        "char" | "varchar" | "nchar" | "nvarchar" | "binary" | "varbinary" => {
+            match column.character_maximum_length {
+                Some(-1) => format!("{}(max)", column.data_type),
+                Some(max_length) => format!("{}({})", column.data_type, max_length),
+                None => column.data_type.clone(),
+            }
+        }
+        "decimal" | "numeric" => match column.numeric_precision {
+            Some(precision) => format!(
+                "{}({},{})",
+                column.data_type,
+                precision,
+                column.numeric_scale.unwrap_or(0)
+            ),
+            None => column.data_type.clone(),
+        },
+        _ => column.data_type.clone(),
+    }
+}
+
+/// GitHub style heading anchor.
+fn get_anchor(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace('<', "&lt;")
+        .replace(['\r', '\n'], " ")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
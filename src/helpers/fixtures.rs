@@ -0,0 +1,54 @@
+use crate::helpers::{
+    resolve_names::resolve_names,
+    structs::{NamingConfig, Table},
+};
+
+/// Returns the `jkrtdb.dbo` base table with `(column_name, data_type, is_nullable)` columns.
+pub fn make_table(table_name: &str, columns: &[(&str, &str, &str)]) -> Table {
+    make_table_in("jkrtdb", table_name, columns)
+}
+
+/// [`make_table`] in the `dbo` schema of `table_catalog`.
+pub fn make_table_in(
+    table_catalog: &str,
+    table_name: &str,
+    columns: &[(&str, &str, &str)],
+) -> Table {
+    let columns: Vec<serde_json::Value> = columns
+        .iter()
+        .enumerate()
+        .map(|(index, (column_name, data_type, is_nullable))| {
+            serde_json::json!({
+                "TABLE_CATALOG": table_catalog,
+                "TABLE_SCHEMA": "dbo",
+                "TABLE_NAME": table_name,
+                "COLUMN_NAME": column_name,
+                "ORDINAL_POSITION": index + 1,
+                "IS_NULLABLE": is_nullable,
+                "DATA_TYPE": data_type,
+            })
+        })
+        .collect();
+    serde_json::from_value(serde_json::json!({
+        "name": {
+            "TABLE_CATALOG": table_catalog,
+            "TABLE_SCHEMA": "dbo",
+            "TABLE_NAME": table_name,
+            "TABLE_TYPE": "BASE TABLE",
+        },
+        "columns": columns,
+    }))
+    .unwrap()
+}
+
+/// Resolves the generated names of `table_list` with the default naming.
+pub fn resolve_tables(mut table_list: Vec<Table>, use_split_file: bool) -> Vec<Table> {
+    resolve_names(
+        &mut table_list,
+        &NamingConfig::default(),
+        use_split_file,
+        &[],
+    )
+    .unwrap();
+    table_list
+}
@@ -0,0 +1,114 @@
+use std::{collections::HashMap, path::PathBuf};
+
+#[cfg(feature = "templates")]
+use crate::helpers::template_writer::TemplateGenerator;
+use crate::helpers::{
+    csharp_file_writer::CSharpGenerator,
+    dart_file_writer::DartGenerator,
+    diagram_writer::DiagramGenerator,
+    docs_writer::DocsGenerator,
+    json_schema_writer::JsonSchemaGenerator,
+    proto_file_writer::ProtoGenerator,
+    python_file_writer::PythonGenerator,
+    rs_file_writer::RustGenerator,
+    signal_file_writer::SignalGenerator,
+    structs::{GeneratorOptions, Table},
+    traits::code_generator::CodeGenerator,
+    ts_file_writer::TypeScriptGenerator,
+};
+
+#[test]
+fn test_get_code_generators() {
+    let generators = get_code_generators(&["signals".into(), "rust".into()]).unwrap();
+    let names: Vec<&str> = generators
+        .iter()
+        .map(|generator| generator.name())
+        .collect();
+    assert_eq!(names, vec!["signals", "rust"]);
+
+    let error = get_code_generators(&["rust".into(), "java".into()])
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("unknown generator `java`"));
+
+    let error = get_code_generators(&["rust".into(), " rust".into()])
+        .err()
+        .unwrap();
+    assert_eq!(error.to_string(), "generator `rust` is listed twice");
+}
+
+/// Every generator, selected by `generators = [...]`.
+pub fn get_registry() -> Vec<Box<dyn CodeGenerator>> {
+    vec![
+        Box::new(RustGenerator),
+        Box::new(SignalGenerator),
+        #[cfg(feature = "templates")]
+        Box::new(TemplateGenerator),
+        Box::new(TypeScriptGenerator),
+        Box::new(DartGenerator),
+        Box::new(ProtoGenerator),
+        Box::new(JsonSchemaGenerator),
+        Box::new(CSharpGenerator),
+        Box::new(PythonGenerator),
+        Box::new(DocsGenerator),
+        Box::new(DiagramGenerator),
+    ]
+}
+
+/// Returns the generators of `names` in the given order.
+pub fn get_code_generators(
+    names: &[String],
+) -> Result<Vec<Box<dyn CodeGenerator>>, Box<dyn std::error::Error>> {
+    let mut registry = get_registry();
+    let mut generators = vec![];
+    for name in names {
+        let name = name.trim();
+        match registry
+            .iter()
+            .position(|generator| generator.name() == name)
+        {
+            Some(index) => generators.push(registry.remove(index)),
+            None if generators.iter().any(|generator| generator.name() == name) => {
+                return Err(format!("generator `{}` is listed twice", name).into());
+            }
+            None => {
+                let names: Vec<&str> = get_registry()
+                    .iter()
+                    .map(|generator| generator.name())
+                    .collect();
+                return Err(format!(
+                    "unknown generator `{}`, expected one of {}",
+                    name,
+                    names.join(", ")
+                )
+                .into());
+            }
+        }
+    }
+    Ok(generators)
+}
+
+/// Runs the generators and fails when two of them write the same path.
+pub fn generate_files(
+    generators: &[Box<dyn CodeGenerator>],
+    table_list: &[Table],
+    options: &GeneratorOptions,
+) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+    let mut file_list: HashMap<PathBuf, String> = HashMap::new();
+    let mut writers: HashMap<PathBuf, &str> = HashMap::new();
+    for generator in generators {
+        for (path, file) in generator.generate(table_list, options)? {
+            if let Some(writer) = writers.insert(path.clone(), generator.name()) {
+                return Err(format!(
+                    "{} and {} both write {:?}, set another path for one of them",
+                    writer,
+                    generator.name(),
+                    path
+                )
+                .into());
+            }
+            file_list.insert(path, file);
+        }
+    }
+    Ok(file_list)
+}
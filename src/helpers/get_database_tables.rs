@@ -9,8 +9,18 @@ use tokio_util::compat::Compat;
 
 use crate::helpers::traits::select_parser::{SelectParser, SelectParserTrait};
 
+#[derive(Debug)]
+pub struct DatabaseSchema<'a> {
+    pub tables: SelectParser<'a>,
+    pub columns: SelectParser<'a>,
+    pub key_columns: SelectParser<'a>,
+    pub column_properties: SelectParser<'a>,
+    pub foreign_key_columns: SelectParser<'a>,
+    pub descriptions: SelectParser<'a>,
+}
+
 pub async fn get_database_tables<'a>(
-    tx: mpsc::Sender<(SelectParser<'a>, SelectParser<'a>)>,
+    tx: mpsc::Sender<DatabaseSchema<'a>>,
     database_name: String,
     client: Arc<Mutex<Client<Compat<TcpStream>>>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -42,6 +52,125 @@ pub async fn get_database_tables<'a>(
         .into_results()
         .await?
         .select_parser();
-    let _ = tx.send((tables, columns)).await;
+
+    let key_columns = client
+        .simple_query(format!(
+            "SELECT
+                kcu.TABLE_CATALOG,
+                kcu.TABLE_SCHEMA,
+                kcu.TABLE_NAME,
+                kcu.COLUMN_NAME,
+                kcu.ORDINAL_POSITION
+            FROM
+            {0}.INFORMATION_SCHEMA.TABLE_CONSTRAINTS tc
+            INNER JOIN {0}.INFORMATION_SCHEMA.KEY_COLUMN_USAGE kcu
+                ON tc.CONSTRAINT_SCHEMA = kcu.CONSTRAINT_SCHEMA
+                AND tc.CONSTRAINT_NAME = kcu.CONSTRAINT_NAME
+            WHERE tc.CONSTRAINT_TYPE = 'PRIMARY KEY'
+            ",
+            database_name
+        ))
+        .await?
+        .into_results()
+        .await?
+        .select_parser();
+
+    let column_properties = client
+        .simple_query(format!(
+            "SELECT
+                s.name AS TABLE_SCHEMA,
+                o.name AS TABLE_NAME,
+                c.name AS COLUMN_NAME,
+                c.is_identity AS IS_IDENTITY,
+                c.is_computed AS IS_COMPUTED
+            FROM
+            {0}.sys.columns c
+            INNER JOIN {0}.sys.objects o
+                ON c.object_id = o.object_id
+            INNER JOIN {0}.sys.schemas s
+                ON o.schema_id = s.schema_id
+            WHERE o.type IN ('U', 'V')
+            ",
+            database_name
+        ))
+        .await?
+        .into_results()
+        .await?
+        .select_parser();
+
+    let foreign_key_columns = client
+        .simple_query(format!(
+            "SELECT
+                fk.name AS CONSTRAINT_NAME,
+                ps.name AS TABLE_SCHEMA,
+                pt.name AS TABLE_NAME,
+                pc.name AS COLUMN_NAME,
+                rs.name AS REFERENCED_TABLE_SCHEMA,
+                rt.name AS REFERENCED_TABLE_NAME,
+                rc.name AS REFERENCED_COLUMN_NAME,
+                fkc.constraint_column_id AS ORDINAL_POSITION
+            FROM
+            {0}.sys.foreign_keys fk
+            INNER JOIN {0}.sys.foreign_key_columns fkc
+                ON fk.object_id = fkc.constraint_object_id
+            INNER JOIN {0}.sys.tables pt
+                ON fkc.parent_object_id = pt.object_id
+            INNER JOIN {0}.sys.schemas ps
+                ON pt.schema_id = ps.schema_id
+            INNER JOIN {0}.sys.columns pc
+                ON fkc.parent_object_id = pc.object_id
+                AND fkc.parent_column_id = pc.column_id
+            INNER JOIN {0}.sys.tables rt
+                ON fkc.referenced_object_id = rt.object_id
+            INNER JOIN {0}.sys.schemas rs
+                ON rt.schema_id = rs.schema_id
+            INNER JOIN {0}.sys.columns rc
+                ON fkc.referenced_object_id = rc.object_id
+                AND fkc.referenced_column_id = rc.column_id
+            ",
+            database_name
+        ))
+        .await?
+        .into_results()
+        .await?
+        .select_parser();
+
+    // MS_Description of tables and views, COLUMN_NAME is NULL for the table itself.
+    let descriptions = client
+        .simple_query(format!(
+            "SELECT
+                s.name AS TABLE_SCHEMA,
+                o.name AS TABLE_NAME,
+                c.name AS COLUMN_NAME,
+                CAST(ep.value AS nvarchar(max)) AS DESCRIPTION
+            FROM
+            {0}.sys.extended_properties ep
+            INNER JOIN {0}.sys.objects o
+                ON ep.major_id = o.object_id
+            INNER JOIN {0}.sys.schemas s
+                ON o.schema_id = s.schema_id
+            LEFT JOIN {0}.sys.columns c
+                ON ep.major_id = c.object_id
+                AND ep.minor_id = c.column_id
+            WHERE ep.class = 1
+                AND ep.name = 'MS_Description'
+                AND o.type IN ('U', 'V')
+            ",
+            database_name
+        ))
+        .await?
+        .into_results()
+        .await?
+        .select_parser();
+    let _ = tx
+        .send(DatabaseSchema {
+            tables,
+            columns,
+            key_columns,
+            column_properties,
+            foreign_key_columns,
+            descriptions,
+        })
+        .await;
     Ok(())
 }
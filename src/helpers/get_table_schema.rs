@@ -3,165 +3,99 @@ use tiberius::ColumnData;
 
 use crate::helpers::traits::select_parser::SelectParser;
 use serde::de::DeserializeOwned;
-use serde_json::Value;
+use serde_json::{Map, Value};
+
+#[test]
+fn test_get_table_schema() {
+    use crate::helpers::structs::DescriptionProperty;
+    use tiberius::{Column, ColumnType};
+
+    let parser = SelectParser {
+        columns: ["TABLE_SCHEMA", "TABLE_NAME", "COLUMN_NAME", "DESCRIPTION"]
+            .iter()
+            .map(|name| Column::new(name.to_string(), ColumnType::NVarchar))
+            .collect(),
+        rows: vec![vec![
+            ColumnData::String(Some("dbo".into())),
+            ColumnData::String(Some("ritem".into())),
+            ColumnData::String(None),
+            ColumnData::String(Some("C:\\menu \"items\"\r\n\u{1}".into())),
+        ]],
+    };
+    let descriptions = parser.get_table_schema::<DescriptionProperty>().unwrap();
+    assert_eq!(descriptions[0].column_name, None);
+    assert_eq!(
+        descriptions[0].description.as_deref(),
+        Some("C:\\menu \"items\"\r\n\u{1}")
+    );
+
+    // A row that does not match the struct is an error.
+    assert!(parser.get_table_schema::<(i32, i32)>().is_err());
+}
+
 pub trait GetTableSchema {
-    fn get_table_schema<T>(self: &Self) -> Vec<T>
+    fn get_table_schema<T>(&self) -> Result<Vec<T>, serde_json::Error>
     where
         T: DeserializeOwned;
 }
 
 impl GetTableSchema for SelectParser<'_> {
-    fn get_table_schema<T>(self: &Self) -> Vec<T>
+    /// Deserializes every row into `T` with the column names as keys.
+    fn get_table_schema<T>(&self) -> Result<Vec<T>, serde_json::Error>
     where
         T: DeserializeOwned,
     {
-        let mut select_list: Vec<T> = Vec::new();
-        for row in self.rows.iter() {
-            let mut json: String = "{".into();
-            let mut index = 0;
-            let total_count = &self.columns.len();
-            let mut string_columns: Vec<&str> = Vec::new();
-            for column in &self.columns {
-                if let Some(row_data) = row.get(index) {
-                    match row_data {
-                        ColumnData::String(Some(data)) => {
-                            json.push_str(
-                                format!(
-                                    "\"{}\": \"{}\"",
-                                    column.name(),
-                                    data.replace("\"", "\\\"")
-                                        .replace("\n", "\\\\n")
-                                        .replace("\r", "\\\\r")
-                                        .replace("\t", "\\\\t"),
-                                )
-                                .as_str(),
-                            );
-                            string_columns.push(column.name());
-                        }
-                        ColumnData::U8(Some(data)) => {
-                            json.push_str(
-                                format!("\"{}\": {}", column.name(), data.to_string()).as_str(),
-                            );
-                        }
-                        ColumnData::I16(Some(data)) => {
-                            json.push_str(
-                                format!("\"{}\": {}", column.name(), data.to_string()).as_str(),
-                            );
-                        }
-                        ColumnData::I32(Some(data)) => {
-                            json.push_str(
-                                format!("\"{}\": {}", column.name(), data.to_string()).as_str(),
-                            );
-                        }
-                        ColumnData::I64(Some(data)) => {
-                            json.push_str(
-                                format!("\"{}\": {}", column.name(), data.to_string()).as_str(),
-                            );
-                        }
-                        ColumnData::F32(Some(data)) => {
-                            json.push_str(
-                                format!("\"{}\": {}", column.name(), data.to_string()).as_str(),
-                            );
-                        }
-                        ColumnData::F64(Some(data)) => {
-                            json.push_str(
-                                format!("\"{}\": {}", column.name(), data.to_string()).as_str(),
-                            );
-                        }
-                        ColumnData::Numeric(Some(data)) => {
-                            json.push_str(
-                                format!("\"{}\": {}", column.name(), data.to_string()).as_str(),
-                            );
-                        }
-                        ColumnData::DateTime(Some(data)) => {
-                            let date = DateTime::from_timestamp(
-                                (data.days() as i64) * 24 * 60 * 60
-                                    + (data.seconds_fragments() as i64) / 300,
-                                0,
-                            )
-                            .unwrap();
-                            let date = date.checked_sub_months(Months::new(840)).unwrap();
-                            json.push_str(
-                                format!("\"{}\": {}", column.name(), date.timestamp().to_string())
-                                    .as_str(),
-                            );
-                        }
-                        ColumnData::DateTimeOffset(Some(data)) => {
-                            let seconds = (data.datetime2().time().increments() as i64)
-                                / i64::pow(10, data.datetime2().time().scale() as u32);
-                            let milliseconds = ((data.datetime2().time().increments() as i64)
-                                % i64::pow(10, data.datetime2().time().scale() as u32))
-                                * 100;
-                            let milliseconds = milliseconds - milliseconds % 1000;
-                            let date = DateTime::from_timestamp(
-                                (data.datetime2().date().days() as i64) * 24 * 60 * 60
-                                    - (get_days_from_years(1969) * 24 * 60 * 60)
-                                    + seconds,
-                                milliseconds as u32,
-                            )
-                            .unwrap();
-                            json.push_str(
-                                format!("\"{}\": {}", column.name(), date.timestamp().to_string())
-                                    .as_str(),
-                            );
-                        }
-                        ColumnData::Bit(data) => {
-                            json.push_str(
-                                format!(
-                                    "\"{}\": {}",
-                                    column.name(),
-                                    match data {
-                                        Some(child_data) => match child_data {
-                                            true => "true",
-                                            false => "false",
-                                        },
-                                        None => "false",
-                                    }
-                                )
-                                .as_str(),
-                            );
-                        }
-                        _ => {
-                            json.push_str(format!("\"{}\": null", column.name()).as_str());
-                        }
-                    }
-                }
+        self.rows
+            .iter()
+            .map(|row| {
+                let json: Map<String, Value> = self
+                    .columns
+                    .iter()
+                    .zip(row.iter())
+                    .map(|(column, row_data)| (column.name().to_string(), get_json_value(row_data)))
+                    .collect();
+                serde_json::from_value(Value::Object(json))
+            })
+            .collect()
+    }
+}
 
-                if index == total_count - 1 {
-                    json.push_str("}");
-                    break;
-                } else {
-                    json.push_str(",");
-                }
-                index += 1;
-            }
-            match serde_json::from_str::<Value>(&json) {
-                Ok(mut json) => {
-                    for column in string_columns.iter() {
-                        if let Some(value) = json.get_mut(column) {
-                            if value.is_string() {
-                                *value = Value::String(
-                                    value
-                                        .as_str()
-                                        .unwrap()
-                                        .replace("\\\"", "\"")
-                                        .replace("\\\\n", "\n")
-                                        .replace("\\\\r", "\r")
-                                        .replace("\\\\t", "\t")
-                                        .to_string(),
-                                );
-                            }
-                        }
-                    }
-                    let json: T = serde_json::from_value(json).unwrap();
-                    select_list.push(json);
-                }
-                Err(e) => {
-                    println!("make json Error: {}", e);
-                }
-            };
+fn get_json_value(row_data: &ColumnData) -> Value {
+    match row_data {
+        ColumnData::String(Some(data)) => Value::from(data.as_ref()),
+        ColumnData::U8(Some(data)) => Value::from(*data),
+        ColumnData::I16(Some(data)) => Value::from(*data),
+        ColumnData::I32(Some(data)) => Value::from(*data),
+        ColumnData::I64(Some(data)) => Value::from(*data),
+        ColumnData::F32(Some(data)) => Value::from(*data),
+        ColumnData::F64(Some(data)) => Value::from(*data),
+        ColumnData::Numeric(Some(data)) => data
+            .to_string()
+            .parse::<serde_json::Number>()
+            .map_or(Value::Null, Value::Number),
+        ColumnData::DateTime(Some(data)) => DateTime::from_timestamp(
+            (data.days() as i64) * 24 * 60 * 60 + (data.seconds_fragments() as i64) / 300,
+            0,
+        )
+        .and_then(|date| date.checked_sub_months(Months::new(840)))
+        .map_or(Value::Null, |date| Value::from(date.timestamp())),
+        ColumnData::DateTimeOffset(Some(data)) => {
+            let seconds = (data.datetime2().time().increments() as i64)
+                / i64::pow(10, data.datetime2().time().scale() as u32);
+            let milliseconds = ((data.datetime2().time().increments() as i64)
+                % i64::pow(10, data.datetime2().time().scale() as u32))
+                * 100;
+            let milliseconds = milliseconds - milliseconds % 1000;
+            DateTime::from_timestamp(
+                (data.datetime2().date().days() as i64) * 24 * 60 * 60
+                    - (get_days_from_years(1969) * 24 * 60 * 60)
+                    + seconds,
+                milliseconds as u32,
+            )
+            .map_or(Value::Null, |date| Value::from(date.timestamp()))
         }
-        select_list
+        ColumnData::Bit(data) => Value::Bool(data.unwrap_or(false)),
+        _ => Value::Null,
     }
 }
 
@@ -0,0 +1,233 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use serde_json::{json, Map, Value};
+
+#[cfg(test)]
+use crate::helpers::fixtures::{make_table, resolve_tables};
+use crate::helpers::{
+    common::{get_output_path, get_rust_type, get_split_file_path, get_table_names},
+    structs::{ColumnName, GeneratorOptions, Table},
+    traits::code_generator::CodeGenerator,
+};
+
+#[test]
+fn test_make_json_schema() {
+    let mut table_list = resolve_tables(
+        vec![make_table(
+            "ritem",
+            &[
+                ("ItemNo", "int", "NO"),
+                ("Item Name", "nvarchar", "YES"),
+                ("Price", "decimal", "NO"),
+                ("RegDate", "datetime", "YES"),
+            ],
+        )],
+        false,
+    );
+    table_list[0].columns[1].character_maximum_length = Some(50);
+    table_list[0].columns[2].numeric_precision = Some(10);
+    table_list[0].columns[2].numeric_scale = Some(2);
+
+    let schema = make_json_schema(&table_list[0]);
+    assert_eq!(schema["title"], "JkrtdbDboRitem");
+    assert_eq!(schema["required"], json!(["ItemNo", "Price"]));
+    assert_eq!(schema["additionalProperties"], false);
+    let properties = &schema["properties"];
+    assert_eq!(
+        properties["ItemNo"],
+        json!({ "type": "integer", "minimum": i32::MIN, "maximum": i32::MAX })
+    );
+    assert_eq!(
+        properties["Item Name"],
+        json!({ "type": ["string", "null"], "maxLength": 50 })
+    );
+    assert_eq!(properties["Price"]["pattern"], "^-?\\d{1,8}(\\.\\d{1,2})?$");
+    // decimal(2,2) has no integer digits, only a zero before the point.
+    assert_eq!(get_decimal_pattern(2, 2), "^-?0(\\.\\d{1,2})?$");
+    assert_eq!(
+        properties["RegDate"],
+        json!({ "type": ["integer", "null"] })
+    );
+
+    let file_list = JsonSchemaGenerator
+        .generate(
+            &table_list,
+            &GeneratorOptions {
+                json_schema_path: Some("schemas".into()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let schema: Value =
+        serde_json::from_str(&file_list[&PathBuf::from("schemas/jkrtdb_dbo_ritem.schema.json")])
+            .unwrap();
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+    let openapi: Value =
+        serde_json::from_str(&file_list[&PathBuf::from("schemas/openapi.json")]).unwrap();
+    assert_eq!(
+        openapi["components"]["schemas"]["JkrtdbDboRitem"]["required"],
+        json!(["ItemNo", "Price"])
+    );
+}
+
+/// A JSON Schema per table and an OpenAPI document with every table in
+/// `components/schemas`, for the json of the rust structs.
+pub struct JsonSchemaGenerator;
+
+impl CodeGenerator for JsonSchemaGenerator {
+    fn name(&self) -> &'static str {
+        "json_schema"
+    }
+
+    fn generate(
+        &self,
+        table_list: &[Table],
+        options: &GeneratorOptions,
+    ) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+        let path = get_output_path(&options.json_schema_path, "json_schema")?;
+        let mut file_list: HashMap<PathBuf, String> = HashMap::new();
+        let mut schemas = Map::new();
+
+        for table in table_list {
+            let (table_name, file_name, _) = get_table_names(table);
+            let schema = make_json_schema(table);
+
+            let mut file = Map::new();
+            file.insert(
+                "$schema".into(),
+                "https://json-schema.org/draft/2020-12/schema".into(),
+            );
+            file.extend(schema.as_object().cloned().unwrap_or_default());
+            let current_path = get_split_file_path(
+                &path,
+                table,
+                &options.split_directorys,
+                &format!("{}.schema.json", file_name),
+            );
+            file_list.insert(
+                current_path,
+                format!("{}\n", serde_json::to_string_pretty(&file)?),
+            );
+            schemas.insert(table_name, schema);
+        }
+
+        let openapi = json!({
+            "openapi": "3.1.0",
+            "info": {
+                "title": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "components": {
+                "schemas": schemas,
+            },
+        });
+        file_list.insert(
+            path.join("openapi.json"),
+            format!("{}\n", serde_json::to_string_pretty(&openapi)?),
+        );
+        Ok(file_list)
+    }
+}
+
+/// Keys are the original column names like the serde rename of the rust structs.
+fn make_json_schema(table: &Table) -> Value {
+    let mut columns: Vec<&ColumnName> = table.columns.iter().collect();
+    columns.sort_by_key(|column| column.ordinal_position);
+
+    let mut properties = Map::new();
+    let mut required: Vec<&str> = vec![];
+    for column in columns {
+        let mut schema = make_column_schema(column);
+        if let Some(description) = &column.description {
+            schema["description"] = description.as_str().into();
+        }
+        properties.insert(column.column_name.clone(), schema);
+        if column.is_nullable != "YES" {
+            required.push(&column.column_name);
+        }
+    }
+
+    let mut schema = json!({
+        "title": table.idents.struct_name,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    });
+    if let Some(description) = &table.description {
+        schema["description"] = description.as_str().into();
+    }
+    schema
+}
+
+fn make_column_schema(column: &ColumnName) -> Value {
+    let max_length = column
+        .character_maximum_length
+        .filter(|max_length| *max_length > 0);
+    let mut schema = match get_rust_type(&column.data_type) {
+        "bool" => json!({ "type": "boolean" }),
+        "u8" => json!({ "type": "integer", "minimum": u8::MIN, "maximum": u8::MAX }),
+        "i16" => json!({ "type": "integer", "minimum": i16::MIN, "maximum": i16::MAX }),
+        "i32" => json!({ "type": "integer", "minimum": i32::MIN, "maximum": i32::MAX }),
+        "i64" => json!({ "type": "integer", "minimum": i64::MIN, "maximum": i64::MAX }),
+        "f32" | "f64" => match column.data_type.as_str() {
+            // The money range rounded to f64.
+            "money" => json!({
+                "type": "number",
+                "minimum": -922_337_203_685_477.6,
+                "maximum": 922_337_203_685_477.6,
+            }),
+            _ => json!({ "type": "number" }),
+        },
+        // The rust structs serialize datetime with ts_seconds.
+        "DateTime<Utc>" => json!({ "type": "integer" }),
+        "Vec<u8>" => {
+            let mut schema = json!({
+                "type": "array",
+                "items": { "type": "integer", "minimum": u8::MIN, "maximum": u8::MAX },
+            });
+            if let Some(max_length) = max_length {
+                schema["maxItems"] = max_length.into();
+            }
+            schema
+        }
+        _ => match column.data_type.as_str() {
+            "decimal" | "numeric" => match column.numeric_precision {
+                Some(precision) => json!({
+                    "type": "string",
+                    "pattern": get_decimal_pattern(precision, column.numeric_scale.unwrap_or(0)),
+                }),
+                None => json!({ "type": "string" }),
+            },
+            "uniqueidentifier" => json!({ "type": "string", "format": "uuid" }),
+            "date" => json!({ "type": "string", "format": "date" }),
+            _ => match max_length {
+                Some(max_length) => json!({ "type": "string", "maxLength": max_length }),
+                None => json!({ "type": "string" }),
+            },
+        },
+    };
+    if column.is_nullable == "YES" {
+        let data_type = schema["type"].clone();
+        schema["type"] = json!([data_type, "null"]);
+    }
+    schema
+}
+
+/// decimal and numeric are text in the rust structs, the pattern limits the digits
+/// before and after the point to the precision and scale.
+fn get_decimal_pattern(precision: u8, scale: i32) -> String {
+    let scale = scale.max(0) as u32;
+    let integer_digits = (precision as u32).saturating_sub(scale);
+    let integer_part = match integer_digits {
+        0 => "0".to_string(),
+        integer_digits => format!("\\d{{1,{}}}", integer_digits),
+    };
+    match scale {
+        0 => format!("^-?{}$", integer_part),
+        scale => format!("^-?{}(\\.\\d{{1,{}}})?$", integer_part, scale),
+    }
+}
@@ -1,10 +1,28 @@
 pub mod args_parser;
+#[cfg(feature = "connect")]
 pub mod auth_handler;
 pub mod common;
 pub mod config;
+pub mod csharp_file_writer;
+pub mod dart_file_writer;
+pub mod diagram_writer;
+pub mod docs_writer;
+#[cfg(test)]
+pub mod fixtures;
+pub mod generators;
+#[cfg(feature = "connect")]
 pub mod get_database_tables;
+#[cfg(feature = "connect")]
 pub mod get_table_schema;
+pub mod json_schema_writer;
+pub mod proto_file_writer;
+pub mod python_file_writer;
+pub mod resolve_names;
 pub mod rs_file_writer;
+pub mod schema;
 pub mod signal_file_writer;
 pub mod structs;
+#[cfg(feature = "templates")]
+pub mod template_writer;
 pub mod traits;
+pub mod ts_file_writer;
@@ -0,0 +1,287 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use heck::ToSnakeCase;
+
+#[cfg(test)]
+use crate::helpers::fixtures::{make_table, resolve_tables};
+use crate::helpers::{
+    common::{get_output_path, get_split_file_path, get_table_names, make_comment},
+    config::{PROTO_LOCK_FILE_NAME, STRUCT_PROTO_FILE_NAME},
+    structs::{ColumnName, GeneratorOptions, ProtoLock, ProtoLockTable, Table},
+    traits::code_generator::CodeGenerator,
+};
+
+#[test]
+fn test_make_message_keeps_field_numbers() {
+    let make_table = |columns: &[(&str, &str, &str)]| -> Table {
+        resolve_tables(vec![make_table("ritem", columns)], false).remove(0)
+    };
+
+    let mut lock = ProtoLock::default();
+    let table = make_table(&[
+        ("ItemNo", "int", "NO"),
+        ("Item Name", "nvarchar", "YES"),
+        ("RegDate", "datetime", "YES"),
+    ]);
+    let file = make_proto_file(&Some("jkrtdb".into()), &[&table], &mut lock);
+    assert!(file.starts_with(
+        "syntax = \"proto3\";\n\npackage jkrtdb;\n\nimport \"google/protobuf/timestamp.proto\";\n"
+    ));
+    assert!(file.contains("  int32 item_no = 1;\n"));
+    assert!(file.contains("  optional string item_name = 2;\n"));
+    assert!(file.contains("  google.protobuf.Timestamp reg_date = 3;\n"));
+
+    // A dropped column stays reserved and a new column takes the next number.
+    let table = make_table(&[
+        ("Price", "money", "NO"),
+        ("ItemNo", "int", "NO"),
+        ("RegDate", "datetime", "YES"),
+    ]);
+    let file = make_proto_file(&None, &[&table], &mut lock);
+    assert!(file.contains(
+        "message JkrtdbDboRitem {\n  double price = 4;\n  int32 item_no = 1;\n  google.protobuf.Timestamp reg_date = 3;\n  reserved 2;\n}\n"
+    ));
+    assert_eq!(lock.tables["jkrtdb_dbo_ritem"].fields["Item Name"], 2);
+    assert_eq!(
+        toml::from_str::<ProtoLock>(&toml::to_string(&lock).unwrap()).unwrap(),
+        lock
+    );
+}
+
+#[test]
+fn test_make_proto_file_writes_ascii_names() {
+    let mut table_list = vec![make_table("가격", &[("금액", "int", "NO")])];
+    let naming: crate::helpers::structs::NamingConfig =
+        toml::from_str("unicode = \"keep\"").unwrap();
+    crate::helpers::resolve_names::resolve_names(&mut table_list, &naming, false, &[]).unwrap();
+    assert_eq!(table_list[0].idents.struct_name, "JkrtdbDbo가격");
+
+    let file = make_proto_file(&None, &[&table_list[0]], &mut ProtoLock::default());
+    assert!(file.contains("message JkrtdbDbogagyeog {\n  int32 geumaeg = 1;\n}\n"));
+
+    assert!(validate_proto_package("jkrtdb.v1").is_ok());
+    for package in ["jkrtdb-v1", "1jkrtdb", "jkrtdb..v1", "가격"] {
+        assert!(validate_proto_package(package).is_err());
+    }
+}
+
+/// proto3 messages of every table, with field numbers kept in `proto_lock_path`.
+pub struct ProtoGenerator;
+
+impl CodeGenerator for ProtoGenerator {
+    fn name(&self) -> &'static str {
+        "proto"
+    }
+
+    fn generate(
+        &self,
+        table_list: &[Table],
+        options: &GeneratorOptions,
+    ) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+        if let Some(package) = &options.proto_package {
+            validate_proto_package(package)?;
+        }
+        let lock_path = get_output_path(&options.proto_lock_path, PROTO_LOCK_FILE_NAME)?;
+        let mut lock = load_proto_lock(&lock_path)?;
+
+        let mut file_list: HashMap<PathBuf, String> = HashMap::new();
+        match options.use_split_file {
+            true => {
+                let path = get_output_path(&options.proto_path, "proto")?;
+                for table in table_list {
+                    let (_, file_name, _) = get_table_names(table);
+                    let current_path = get_split_file_path(
+                        &path,
+                        table,
+                        &options.split_directorys,
+                        &format!("{}.proto", file_name),
+                    );
+                    let file = make_proto_file(&options.proto_package, &[table], &mut lock);
+                    file_list.insert(current_path, file);
+                }
+            }
+            false => {
+                let path = get_output_path(&options.proto_path, STRUCT_PROTO_FILE_NAME)?;
+                let tables: Vec<&Table> = table_list.iter().collect();
+                file_list.insert(
+                    path,
+                    make_proto_file(&options.proto_package, &tables, &mut lock),
+                );
+            }
+        }
+        file_list.insert(lock_path, toml::to_string(&lock)?);
+        Ok(file_list)
+    }
+}
+
+/// Reads the lock of the last run, an empty lock when there is none yet.
+fn load_proto_lock(path: &Path) -> Result<ProtoLock, Box<dyn std::error::Error>> {
+    match path.exists() {
+        true => {
+            let lock = std::fs::read_to_string(path)?;
+            Ok(toml::from_str::<ProtoLock>(&lock)
+                .map_err(|error| format!("failed to parse proto lock {:?}: {}", path, error))?)
+        }
+        false => Ok(ProtoLock::default()),
+    }
+}
+
+fn make_proto_file(
+    package: &Option<String>,
+    table_list: &[&Table],
+    lock: &mut ProtoLock,
+) -> String {
+    let mut message_names: HashSet<String> = HashSet::new();
+    let messages: Vec<String> = table_list
+        .iter()
+        .map(|table| {
+            let (table_name, _, _) = get_table_names(table);
+            let message_name = get_proto_message_name(&table_name, &mut message_names);
+            let lock_table = lock.tables.entry(table.name.get_config_name()).or_default();
+            make_message(&message_name, table, lock_table)
+        })
+        .collect();
+    let messages = messages.join("\n");
+
+    let mut file: String = "syntax = \"proto3\";\n\n".into();
+    if let Some(package) = package {
+        file.push_str(&format!("package {};\n\n", package));
+    }
+    if messages.contains("google.protobuf.Timestamp") {
+        file.push_str("import \"google/protobuf/timestamp.proto\";\n\n");
+    }
+    file.push_str(&messages);
+    file
+}
+
+fn make_message(table_name: &str, table: &Table, lock_table: &mut ProtoLockTable) -> String {
+    let mut columns: Vec<&ColumnName> = table.columns.iter().collect();
+    columns.sort_by_key(|column| column.ordinal_position);
+
+    let mut file = String::new();
+    file.push_str(&make_comment(&table.description, "// "));
+    file.push_str(&format!("message {} {{\n", table_name));
+    let mut field_names: HashSet<String> = HashSet::new();
+    for column in &columns {
+        let field_number = get_field_number(&column.column_name, lock_table);
+        let data_type = get_proto_type(&column.data_type);
+        // Message fields already have presence.
+        let label = match column.is_nullable == "YES" && !data_type.contains('.') {
+            true => "optional ",
+            false => "",
+        };
+        file.push_str(&make_comment(&column.description, "  // "));
+        file.push_str(&format!(
+            "  {}{} {} = {};\n",
+            label,
+            data_type,
+            get_proto_field_name(column, &mut field_names),
+            field_number
+        ));
+    }
+
+    let mut reserved: Vec<u32> = lock_table
+        .fields
+        .iter()
+        .filter(|(column_name, _)| {
+            !columns
+                .iter()
+                .any(|column| &column.column_name == *column_name)
+        })
+        .map(|(_, field_number)| *field_number)
+        .collect();
+    if !reserved.is_empty() {
+        reserved.sort();
+        let reserved: Vec<String> = reserved.iter().map(|number| number.to_string()).collect();
+        file.push_str(&format!("  reserved {};\n", reserved.join(", ")));
+    }
+    file.push_str("}\n");
+    file
+}
+
+/// Number of the column in the lock, or the next free one, skipping the range
+/// protobuf reserves for itself.
+fn get_field_number(column_name: &str, lock_table: &mut ProtoLockTable) -> u32 {
+    if let Some(field_number) = lock_table.fields.get(column_name) {
+        return *field_number;
+    }
+    let mut field_number = lock_table.fields.values().max().map_or(1, |max| max + 1);
+    if (19000..=19999).contains(&field_number) {
+        field_number = 20000;
+    }
+    lock_table.fields.insert(column_name.into(), field_number);
+    field_number
+}
+
+/// `package` is dot separated proto identifiers, like `jkrtdb.v1`.
+fn validate_proto_package(package: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let is_valid = package.split('.').all(|part| {
+        part.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    });
+    match is_valid {
+        true => Ok(()),
+        false => Err(format!(
+            "proto_package `{}` is not a valid proto package",
+            package
+        ))?,
+    }
+}
+
+/// proto identifiers are ASCII whatever `unicode` is, other chars are transliterated
+/// or replaced with `_`.
+fn to_proto_ident(text: &str) -> String {
+    deunicode::deunicode(text)
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() || c == '_' {
+            true => c,
+            false => '_',
+        })
+        .collect()
+}
+
+/// ASCII struct name of the table, unique in the file.
+fn get_proto_message_name(table_name: &str, message_names: &mut HashSet<String>) -> String {
+    let mut message_name = to_proto_ident(table_name);
+    if message_name.is_empty() || message_name.starts_with(|c: char| c.is_ascii_digit()) {
+        message_name = format!("Message{}", message_name);
+    }
+    get_unique_name(message_name, message_names)
+}
+
+/// snake_case of the resolved field name, unique in the message.
+fn get_proto_field_name(column: &ColumnName, field_names: &mut HashSet<String>) -> String {
+    let mut field_name = to_proto_ident(column.field_name.trim_start_matches("r#")).to_snake_case();
+    if field_name.is_empty() || field_name.starts_with(|c: char| c.is_ascii_digit()) {
+        field_name = format!("field_{}", field_name);
+    }
+    get_unique_name(field_name, field_names)
+}
+
+fn get_unique_name(name: String, names: &mut HashSet<String>) -> String {
+    let mut unique_name = name.clone();
+    let mut index = 2;
+    while !names.insert(unique_name.clone()) {
+        unique_name = format!("{}_{}", name, index);
+        index += 1;
+    }
+    unique_name
+}
+
+pub fn get_proto_type(data_type: &str) -> &'static str {
+    match data_type {
+        "bit" => "bool",
+        "tinyint" | "smallint" | "int" => "int32",
+        "bigint" => "int64",
+        "real" => "float",
+        "float" | "money" | "smallmoney" => "double",
+        "date" | "datetime" | "datetime2" | "smalldatetime" | "datetimeoffset" => {
+            "google.protobuf.Timestamp"
+        }
+        "binary" | "varbinary" | "image" | "timestamp" | "rowversion" => "bytes",
+        _ => "string",
+    }
+}
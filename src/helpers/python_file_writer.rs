@@ -0,0 +1,391 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use heck::ToSnakeCase;
+
+#[cfg(test)]
+use crate::helpers::fixtures::{make_table, resolve_tables};
+use crate::helpers::{
+    common::{get_output_path, get_split_file_path, get_table_names, make_comment},
+    config::STRUCT_PYTHON_FILE_NAME,
+    structs::{ColumnName, GeneratorOptions, SplitDirectoryConfig, Table},
+    traits::code_generator::CodeGenerator,
+};
+
+#[test]
+fn test_make_python_file() {
+    let mut table_list = resolve_tables(
+        vec![make_table(
+            "ritem",
+            &[
+                ("ItemNo", "int", "NO"),
+                ("Item Name", "nvarchar", "YES"),
+                ("Price", "decimal", "NO"),
+                ("RegDate", "datetime", "YES"),
+                ("model_no", "int", "NO"),
+                ("Date", "date", "YES"),
+                ("field", "int", "NO"),
+                ("Note\t\"1\"", "nvarchar", "YES"),
+            ],
+        )],
+        true,
+    );
+    table_list[0].columns[1].character_maximum_length = Some(50);
+    table_list[0].columns[2].numeric_precision = Some(10);
+    table_list[0].columns[2].numeric_scale = Some(2);
+    let tables: Vec<&Table> = table_list.iter().collect();
+
+    let file = make_python_file(&tables, false);
+    assert!(file.starts_with("from datetime import date, datetime\nfrom decimal import Decimal\nfrom typing import Optional\n\nfrom pydantic import BaseModel, ConfigDict, Field\n\n\nclass JkrtdbDboRitem(BaseModel):\n"));
+    assert!(
+        file.contains("    item_name: Optional[str] = Field(alias=\"Item Name\", max_length=50)\n")
+    );
+    assert!(file.contains(
+        "    price: Decimal = Field(alias=\"Price\", max_digits=10, decimal_places=2)\n"
+    ));
+    assert!(file.contains("    reg_date: Optional[datetime] = Field(alias=\"RegDate\")\n"));
+    assert!(file.contains("    field_model_no: int = Field(alias=\"model_no\")\n"));
+    assert!(file.contains("    date_: Optional[date] = Field(alias=\"Date\")\n"));
+    assert!(file.contains("Field(alias=\"Note\\t\\\"1\\\"\")\n"));
+    let file = make_python_file(&tables, true);
+    assert!(file.contains("    field_: int = field(metadata={\"column\": \"field\"})\n"));
+    assert!(file.contains(
+        "@dataclass\nclass JkrtdbDboRitem:\n    item_no: int = field(metadata={\"column\": \"ItemNo\"})\n    item_name: Optional[str] = field(metadata={\"column\": \"Item Name\", \"max_length\": 50})\n"
+    ));
+    assert_eq!(
+        make_python_string("가\\\u{7}\u{85}"),
+        "\"가\\\\\\x07\\x85\""
+    );
+
+    let split_directorys = vec![SplitDirectoryConfig {
+        starts_with_name: "jkrtdb_".into(),
+        directory_name: "jkrtdb".into(),
+    }];
+    let file_list = make_python_split_files(
+        &Some("models".into()),
+        false,
+        &table_list,
+        &split_directorys,
+    )
+    .unwrap();
+    assert!(file_list.contains_key(&PathBuf::from("models/jkrtdb/jkrtdb_dbo_ritem.py")));
+    assert_eq!(
+        file_list[&PathBuf::from("models/jkrtdb/__init__.py")],
+        "from .jkrtdb_dbo_ritem import JkrtdbDboRitem\n\n__all__ = [\"JkrtdbDboRitem\"]\n"
+    );
+    assert_eq!(
+        file_list[&PathBuf::from("models/__init__.py")],
+        "from .jkrtdb import JkrtdbDboRitem\n\n__all__ = [\"JkrtdbDboRitem\"]\n"
+    );
+}
+
+/// pydantic models or dataclasses of every table.
+pub struct PythonGenerator;
+
+impl CodeGenerator for PythonGenerator {
+    fn name(&self) -> &'static str {
+        "python"
+    }
+
+    fn generate(
+        &self,
+        table_list: &[Table],
+        options: &GeneratorOptions,
+    ) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+        match options.use_split_file {
+            true => make_python_split_files(
+                &options.python_path,
+                options.use_python_dataclass,
+                table_list,
+                &options.split_directorys,
+            ),
+            false => {
+                let path = get_output_path(&options.python_path, STRUCT_PYTHON_FILE_NAME)?;
+                let tables: Vec<&Table> = table_list.iter().collect();
+                Ok(HashMap::from([(
+                    path,
+                    make_python_file(&tables, options.use_python_dataclass),
+                )]))
+            }
+        }
+    }
+}
+
+fn make_python_split_files(
+    path: &Option<String>,
+    use_python_dataclass: bool,
+    table_list: &[Table],
+    split_directorys: &[SplitDirectoryConfig],
+) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+    let path = get_output_path(path, "models")?;
+    let mut file_list: HashMap<PathBuf, String> = HashMap::new();
+    let mut module_list: Vec<(PathBuf, Vec<String>)> = vec![];
+
+    for table in table_list {
+        let (table_name, file_name, _) = get_table_names(table);
+        let current_path =
+            get_split_file_path(&path, table, split_directorys, &format!("{}.py", file_name));
+        module_list.push((current_path.clone(), vec![table_name]));
+        file_list.insert(
+            current_path,
+            make_python_file(&[table], use_python_dataclass),
+        );
+    }
+    file_list.extend(make_init_files(&path, &module_list));
+    Ok(file_list)
+}
+
+/// Returns `__init__.py` of `root` and every package between it and the modules,
+/// importing the classes of each child and listing them in `__all__`.
+fn make_init_files(
+    root: &Path,
+    module_list: &[(PathBuf, Vec<String>)],
+) -> HashMap<PathBuf, String> {
+    let mut tree: BTreeMap<PathBuf, BTreeMap<String, BTreeSet<String>>> = BTreeMap::new();
+    tree.entry(root.to_path_buf()).or_default();
+    for (file_path, names) in module_list {
+        let mut path = file_path.with_extension("");
+        while let Some(parent) = path.parent() {
+            if !parent.starts_with(root) {
+                break;
+            }
+            let module_name = path.file_name().unwrap().to_string_lossy().to_string();
+            tree.entry(parent.to_path_buf())
+                .or_default()
+                .entry(module_name)
+                .or_default()
+                .extend(names.iter().cloned());
+            path = parent.to_path_buf();
+        }
+    }
+
+    tree.into_iter()
+        .map(|(directory, modules)| {
+            let mut file = String::new();
+            for (module_name, names) in &modules {
+                let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+                file.push_str(&format!(
+                    "from .{} import {}\n",
+                    module_name,
+                    names.join(", ")
+                ));
+            }
+            let names: BTreeSet<String> = modules
+                .values()
+                .flat_map(|names| names.iter().map(|name| make_python_string(name)))
+                .collect();
+            if !names.is_empty() {
+                let names: Vec<String> = names.into_iter().collect();
+                file.push_str(&format!("\n__all__ = [{}]\n", names.join(", ")));
+            }
+            (directory.join("__init__.py"), file)
+        })
+        .collect()
+}
+
+fn make_python_file(table_list: &[&Table], use_python_dataclass: bool) -> String {
+    let classes: Vec<String> = table_list
+        .iter()
+        .map(|table| {
+            let (table_name, _, _) = get_table_names(table);
+            make_class(&table_name, table, use_python_dataclass)
+        })
+        .collect();
+    let classes = classes.join("\n\n");
+
+    let mut imports: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for table in table_list {
+        for column in &table.columns {
+            let (module, name) = match get_python_type(&column.data_type) {
+                "datetime" => ("datetime", "datetime"),
+                "date" => ("datetime", "date"),
+                "time" => ("datetime", "time"),
+                "Decimal" => ("decimal", "Decimal"),
+                "UUID" => ("uuid", "UUID"),
+                _ => continue,
+            };
+            imports.entry(module).or_default().insert(name);
+        }
+        if table
+            .columns
+            .iter()
+            .any(|column| column.is_nullable == "YES")
+        {
+            imports.entry("typing").or_default().insert("Optional");
+        }
+    }
+    if use_python_dataclass {
+        imports
+            .entry("dataclasses")
+            .or_default()
+            .extend(["dataclass", "field"]);
+    }
+
+    let mut file = String::new();
+    for (module, names) in &imports {
+        let names: Vec<&str> = names.iter().copied().collect();
+        file.push_str(&format!("from {} import {}\n", module, names.join(", ")));
+    }
+    if !use_python_dataclass {
+        if !imports.is_empty() {
+            file.push('\n');
+        }
+        file.push_str("from pydantic import BaseModel, ConfigDict, Field\n");
+    }
+    file.push_str("\n\n");
+    file.push_str(&classes);
+    file
+}
+
+fn make_class(table_name: &str, table: &Table, use_python_dataclass: bool) -> String {
+    let mut columns: Vec<&ColumnName> = table.columns.iter().collect();
+    columns.sort_by_key(|column| column.ordinal_position);
+
+    let mut file = String::new();
+    file.push_str(&make_comment(&table.description, "# "));
+    match use_python_dataclass {
+        true => file.push_str(&format!("@dataclass\nclass {}:\n", table_name)),
+        false => file.push_str(&format!(
+            "class {}(BaseModel):\n    model_config = ConfigDict(populate_by_name=True)\n\n",
+            table_name
+        )),
+    }
+    let mut field_names: HashSet<String> = HashSet::new();
+    for column in columns {
+        let data_type = get_python_type(&column.data_type);
+        let field_type = match column.is_nullable == "YES" {
+            true => format!("Optional[{}]", data_type),
+            false => data_type.to_string(),
+        };
+
+        let mut arguments: Vec<(&str, String)> = vec![];
+        if let Some(max_length) = column
+            .character_maximum_length
+            .filter(|max_length| *max_length > 0 && matches!(data_type, "str" | "bytes"))
+        {
+            arguments.push(("max_length", max_length.to_string()));
+        }
+        if let (Some(precision), "decimal" | "numeric") =
+            (column.numeric_precision, column.data_type.as_str())
+        {
+            arguments.push(("max_digits", precision.to_string()));
+            arguments.push((
+                "decimal_places",
+                column.numeric_scale.unwrap_or(0).to_string(),
+            ));
+        }
+
+        let field_name = get_python_field_name(column, &mut field_names);
+        let field = match use_python_dataclass {
+            true => {
+                let mut metadata = vec![format!(
+                    "\"column\": {}",
+                    make_python_string(&column.column_name)
+                )];
+                metadata.extend(
+                    arguments
+                        .iter()
+                        .map(|(name, value)| format!("{}: {}", make_python_string(name), value)),
+                );
+                format!("field(metadata={{{}}})", metadata.join(", "))
+            }
+            false => {
+                let mut arguments: Vec<String> = arguments
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, value))
+                    .collect();
+                arguments.insert(
+                    0,
+                    format!("alias={}", make_python_string(&column.column_name)),
+                );
+                format!("Field({})", arguments.join(", "))
+            }
+        };
+        file.push_str(&make_comment(&column.description, "    # "));
+        file.push_str(&format!("    {}: {} = {}\n", field_name, field_type, field));
+    }
+    file
+}
+
+const PYTHON_KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// Names the modules import, a class attribute with one of them would shadow the type
+/// in the annotations after it.
+const PYTHON_IMPORTED_NAMES: [&str; 11] = [
+    "date",
+    "time",
+    "datetime",
+    "Decimal",
+    "UUID",
+    "Optional",
+    "Field",
+    "BaseModel",
+    "ConfigDict",
+    "dataclass",
+    "field",
+];
+
+/// snake_case of the resolved field name, unique in the class. The original column name
+/// is the pydantic alias or the dataclass metadata.
+fn get_python_field_name(column: &ColumnName, field_names: &mut HashSet<String>) -> String {
+    let mut field_name = column.field_name.trim_start_matches("r#").to_snake_case();
+    if field_name.is_empty() || field_name.starts_with(|c: char| c.is_ascii_digit()) {
+        field_name = format!("field_{}", field_name);
+    }
+    // pydantic reserves the model_ prefix.
+    if field_name.starts_with("model_") {
+        field_name = format!("field_{}", field_name);
+    }
+    if PYTHON_KEYWORDS.contains(&field_name.as_str())
+        || PYTHON_IMPORTED_NAMES.contains(&field_name.as_str())
+    {
+        field_name.push('_');
+    }
+    let mut unique_name = field_name.clone();
+    let mut index = 2;
+    while !field_names.insert(unique_name.clone()) {
+        unique_name = format!("{}_{}", field_name, index);
+        index += 1;
+    }
+    unique_name
+}
+
+/// Double quoted python string literal of `value`.
+fn make_python_string(value: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c if c.is_control() => literal.push_str(&format!("\\x{:02x}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+pub fn get_python_type(data_type: &str) -> &'static str {
+    match data_type {
+        "bit" => "bool",
+        "tinyint" | "smallint" | "int" | "bigint" => "int",
+        "real" | "float" => "float",
+        "decimal" | "numeric" | "money" | "smallmoney" => "Decimal",
+        "date" => "date",
+        "datetime" | "datetime2" | "smalldatetime" | "datetimeoffset" => "datetime",
+        "time" => "time",
+        "uniqueidentifier" => "UUID",
+        "binary" | "varbinary" | "image" | "timestamp" | "rowversion" => "bytes",
+        _ => "str",
+    }
+}
@@ -0,0 +1,320 @@
+use std::collections::{BTreeMap, HashSet};
+
+use heck::ToUpperCamelCase;
+
+#[cfg(test)]
+use crate::helpers::fixtures::make_table_in;
+use crate::helpers::{
+    common::{convert_case, convert_identifier_chars, get_split_directory_name, is_rust_keyword},
+    signal_file_writer::get_column_name,
+    structs::{NamingConfig, OnCollision, SplitDirectoryConfig, Table, TableIdents},
+};
+
+#[test]
+fn test_resolve_names() {
+    let table = |table_catalog: &str, column_names: &[&str]| -> Table {
+        let columns: Vec<(&str, &str, &str)> = column_names
+            .iter()
+            .map(|column_name| (*column_name, "int", "NO"))
+            .collect();
+        make_table_in(table_catalog, "ritem", &columns)
+    };
+    let mut table_list = vec![
+        table("jkttdb", &["ItemNo"]),
+        table("jkrtdb", &["Item-No", "Item_No", "Item No"]),
+    ];
+    let naming = NamingConfig::default();
+    resolve_names(&mut table_list, &naming, false, &[]).unwrap();
+
+    assert_eq!(table_list[1].idents.struct_name, "JkrtdbDboRitem");
+    assert_eq!(table_list[1].idents.const_name, "RITEM");
+    assert_eq!(table_list[0].idents.const_name, "JKTTDB_DBO_RITEM");
+    let field_names: Vec<&str> = table_list[1]
+        .columns
+        .iter()
+        .map(|column| column.field_name.as_str())
+        .collect();
+    assert_eq!(field_names, vec!["Item_No", "Item_No2", "Item_No3"]);
+
+    let naming = NamingConfig {
+        on_collision: OnCollision::Error,
+        ..Default::default()
+    };
+    let error = resolve_names(&mut table_list, &naming, false, &[]).unwrap_err();
+    assert!(error.to_string().contains("field `Item_No`"));
+    assert!(error.to_string().contains("const `RITEM_*`"));
+}
+
+#[test]
+fn test_resolve_names_of_lookup_enums() {
+    let lookup_enum = |enum_name: &str| -> Option<crate::helpers::structs::LookupEnum> {
+        serde_json::from_value(serde_json::json!({
+            "key": "Code",
+            "name": "Label",
+            "enum_name": enum_name,
+            "values": [{ "KEY": "1", "NAME": "Cash" }, { "KEY": "2", "NAME": "cash" }],
+        }))
+        .unwrap()
+    };
+    let mut table_list = vec![
+        make_table_in("jkrtdb", "ritem", &[("ItemNo", "int", "NO")]),
+        make_table_in("jkrtdb", "rcode", &[("Code", "int", "NO")]),
+        make_table_in("jkrtdb", "rtype", &[("Code", "int", "NO")]),
+    ];
+    table_list[1].lookup_enum = lookup_enum("JkrtdbDboRitem");
+    table_list[2].lookup_enum = lookup_enum("RITEM_COLUMNS");
+    resolve_names(&mut table_list, &NamingConfig::default(), false, &[]).unwrap();
+
+    assert_eq!(table_list[0].idents.struct_name, "JkrtdbDboRitem");
+    assert_eq!(table_list[1].idents.enum_name, "JkrtdbDboRcodeEnum");
+    assert_eq!(table_list[2].idents.enum_name, "JkrtdbDboRtypeEnum");
+    let variant_names: Vec<&str> = table_list[1]
+        .lookup_enum
+        .as_ref()
+        .unwrap()
+        .values
+        .iter()
+        .map(|value| value.variant_name.as_str())
+        .collect();
+    assert_eq!(variant_names, vec!["Cash", "Cash2"]);
+
+    let naming = NamingConfig {
+        on_collision: OnCollision::Error,
+        ..Default::default()
+    };
+    let error = resolve_names(&mut table_list, &naming, false, &[]).unwrap_err();
+    assert!(error.to_string().contains(
+        "- struct `JkrtdbDboRitem` is generated for jkrtdb.dbo.ritem, enums of jkrtdb.dbo.rcode"
+    ));
+    assert!(error.to_string().contains(
+        "- struct `RITEM_COLUMNS` is generated for consts of jkrtdb.dbo.ritem, enums of jkrtdb.dbo.rtype"
+    ));
+    assert!(error.to_string().contains(
+        "- variant `JkrtdbDboRcodeEnum::cash` is generated for jkrtdb.dbo.rcode.Code = \"1\", jkrtdb.dbo.rcode.Code = \"2\""
+    ));
+}
+
+/// Resolves struct, field, const and module names of every table and
+/// makes them unique, or fails with a report when `on_collision = "error"`.
+pub fn resolve_names(
+    table_list: &mut [Table],
+    naming: &NamingConfig,
+    use_split_file: bool,
+    split_directorys: &[SplitDirectoryConfig],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut collisions: Vec<String> = vec![];
+
+    let mut order: Vec<usize> = (0..table_list.len()).collect();
+    order.sort_by_key(|index| table_list[*index].name.get_config_name());
+
+    let mut struct_names = NameSet::new(false);
+    let mut const_names = NameSet::new(false);
+    let mut file_names = NameSet::new(true);
+
+    for index in order.iter().copied() {
+        let table = &mut table_list[index];
+        let sql_table_name = table.name.get_sql_table_name();
+        let full_parts = [
+            convert_identifier_chars(&table.name.table_catalog, naming),
+            convert_identifier_chars(&table.name.table_schema, naming),
+            convert_identifier_chars(&table.name.table_name, naming),
+        ];
+
+        let struct_name = struct_names.insert(
+            table.name.get_table_name(naming),
+            format!(
+                "{}{}{}",
+                naming.struct_prefix,
+                convert_case(&full_parts, &naming.struct_case),
+                naming.struct_suffix
+            ),
+            "",
+            &sql_table_name,
+        );
+
+        let const_name = match use_split_file {
+            true => table.name.get_const_name(naming),
+            false => const_names.insert(
+                table.name.get_const_name(naming),
+                convert_case(&full_parts, &naming.const_case),
+                "",
+                &sql_table_name,
+            ),
+        };
+
+        let file_name = match use_split_file {
+            true => file_names.insert(
+                table.name.get_file_name(naming),
+                format!(
+                    "{}{}{}",
+                    naming.file_prefix,
+                    convert_case(&full_parts, &naming.file_case),
+                    naming.file_suffix
+                ),
+                get_split_directory_name(table, split_directorys).unwrap_or_default(),
+                &sql_table_name,
+            ),
+            false => table.name.get_file_name(naming),
+        };
+
+        let mut field_names = NameSet::new(false);
+        let mut columns: Vec<_> = table.columns.iter_mut().collect();
+        columns.sort_by_key(|column| column.ordinal_position);
+        for column in columns {
+            let field_name = get_column_name(column, naming);
+            column.field_name = field_names.insert(
+                field_name.clone(),
+                field_name,
+                "",
+                &format!("{}.{}", sql_table_name, column.column_name),
+            );
+        }
+        collisions.extend(field_names.report("field", |name| name.into()));
+
+        table.idents = TableIdents {
+            struct_name,
+            file_name,
+            const_name,
+            enum_name: String::new(),
+        };
+    }
+
+    // Enums are named after every struct, so a struct keeps its name, and never take the
+    // name of a generated const.
+    for table in table_list.iter() {
+        for suffix in ["_TABLE_NAME", "_COLUMNS"] {
+            struct_names.reserve(
+                &format!("{}{}", table.idents.const_name, suffix),
+                "",
+                &format!("consts of {}", table.name.get_sql_table_name()),
+            );
+        }
+    }
+    for index in order {
+        let table = &mut table_list[index];
+        let Some(lookup_enum) = &mut table.lookup_enum else {
+            continue;
+        };
+        let sql_table_name = table.name.get_sql_table_name();
+        let fallback = format!("{}Enum", table.idents.struct_name);
+        table.idents.enum_name = struct_names.insert(
+            lookup_enum.enum_name.clone().unwrap_or(fallback.clone()),
+            fallback,
+            "",
+            &format!("enums of {}", sql_table_name),
+        );
+
+        let mut variant_names = NameSet::new(true);
+        for value in &mut lookup_enum.values {
+            let Some(key) = &value.key else {
+                continue;
+            };
+            let variant_name = get_variant_name(value.name.as_deref().unwrap_or(key));
+            value.variant_name = variant_names.insert(
+                variant_name.clone(),
+                variant_name,
+                "",
+                &format!("{}.{} = {:?}", sql_table_name, lookup_enum.key, key),
+            );
+        }
+        let enum_name = &table.idents.enum_name;
+        collisions
+            .extend(variant_names.report("variant", |name| format!("{}::{}", enum_name, name)));
+    }
+    collisions.extend(struct_names.report("struct", |name| name.into()));
+    collisions.extend(const_names.report("const", |name| format!("{}_*", name)));
+    collisions.extend(file_names.report("module", |name| format!("{}.rs", name)));
+
+    if naming.on_collision == OnCollision::Error && !collisions.is_empty() {
+        return Err(format!("identifier collisions found:\n{}", collisions.join("\n")).into());
+    }
+    Ok(())
+}
+
+/// Keeps the generated names of a scope and the sources that asked for them.
+struct NameSet {
+    ignore_case: bool,
+    used: HashSet<String>,
+    sources: BTreeMap<String, Vec<String>>,
+}
+
+impl NameSet {
+    fn new(ignore_case: bool) -> Self {
+        NameSet {
+            ignore_case,
+            used: HashSet::new(),
+            sources: BTreeMap::new(),
+        }
+    }
+
+    fn key(&self, scope: &str, name: &str) -> String {
+        match self.ignore_case {
+            true => format!("{}/{}", scope, name.to_lowercase()),
+            false => format!("{}/{}", scope, name),
+        }
+    }
+
+    /// Returns `name`, or when taken `fallback`, or a numbered `name`.
+    fn insert(&mut self, name: String, fallback: String, scope: &str, source: &str) -> String {
+        self.sources
+            .entry(self.key(scope, &name))
+            .or_default()
+            .push(source.into());
+
+        let mut candidates = vec![name.clone(), fallback];
+        let mut number = 2;
+        loop {
+            for candidate in candidates.drain(..) {
+                if self.used.insert(self.key(scope, &candidate)) {
+                    return candidate;
+                }
+            }
+            candidates.push(format!("{}{}", name.trim_start_matches("r#"), number));
+            number += 1;
+        }
+    }
+
+    /// Takes `name` for an item named elsewhere, later inserts of it are renamed.
+    fn reserve(&mut self, name: &str, scope: &str, source: &str) {
+        let key = self.key(scope, name);
+        if self.used.insert(key.clone()) {
+            self.sources.entry(key).or_default().push(source.into());
+        }
+    }
+
+    fn report(&self, kind: &str, display: impl Fn(&str) -> String) -> Vec<String> {
+        self.sources
+            .iter()
+            .filter(|(_, sources)| sources.len() > 1)
+            .map(|(key, sources)| {
+                let (scope, name) = key.rsplit_once('/').unwrap();
+                format!(
+                    "- {} `{}`{} is generated for {}",
+                    kind,
+                    display(name),
+                    match scope.is_empty() {
+                        true => "".into(),
+                        false => format!(" in {}", scope),
+                    },
+                    sources.join(", ")
+                )
+            })
+            .collect()
+    }
+}
+
+/// UpperCamelCase of the name column of a lookup table.
+fn get_variant_name(label: &str) -> String {
+    let mut variant_name: String = label
+        .to_upper_camel_case()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    if variant_name.is_empty() || variant_name.starts_with(|c: char| c.is_ascii_digit()) {
+        variant_name = format!("Value{}", variant_name);
+    }
+    if is_rust_keyword(&variant_name) {
+        variant_name.push('_');
+    }
+    variant_name
+}
@@ -1,298 +1,1333 @@
-use std::{collections::HashMap, env, path::PathBuf, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 
-use tokio::{fs::File, io::AsyncWriteExt};
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::quote;
 
-use crate::helpers::{config::STRUCT_FILE_NAME, structs::Table};
+#[cfg(test)]
+use crate::helpers::fixtures::{make_table, resolve_tables};
+use crate::helpers::{
+    config::STRUCT_FILE_NAME,
+    structs::{ColumnName, GeneratorOptions, LookupEnum, Table},
+    traits::code_generator::CodeGenerator,
+};
 
 use super::{
-    common::{get_table_names, write_files},
-    signal_file_writer::get_column_name,
+    common::{
+        format_rust_file, get_field_type, get_output_path, get_rust_type, get_split_file_path,
+        get_table_names, make_doc_attrs, make_ident, make_mod_files, make_type,
+        quote_sql_identifier, unparse_items,
+    },
     structs::SplitDirectoryConfig,
-    traits::StringUtil,
 };
 
-pub async fn rs_file_writer(
-    path: &Option<String>,
-    use_split_file: bool,
-    table_list: &Vec<Table>,
-    split_directorys: &Vec<SplitDirectoryConfig>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    match use_split_file {
-        true => rs_split_file_writer(path, table_list, split_directorys).await?,
-        false => rs_one_file_writer(path, table_list).await?,
+#[test]
+fn test_make_struct_keeps_original_column_names() {
+    let table_list = resolve_tables(
+        vec![make_table(
+            "ritem",
+            &[
+                ("ItemNo", "int", "NO"),
+                ("Unit Price", "int", "NO"),
+                ("1stPrice", "int", "NO"),
+            ],
+        )],
+        false,
+    );
+    let table = &table_list[0];
+
+    let file = unparse_items(make_struct("JkrtdbDboRitem", "jkrtdb.dbo.ritem", table));
+    assert!(!file.contains("#[serde(rename = \"ItemNo\")]"));
+    assert!(file.contains("    #[serde(rename = \"Unit Price\")]\n    pub Unit_Price: i32,\n"));
+    assert!(file.contains("    #[serde(rename = \"1stPrice\")]\n    pub OnestPrice: i32,\n"));
+
+    let file = unparse_items(make_columns("jkrtdb.dbo.ritem", table));
+    assert!(file.contains(
+        "pub const RITEM_COLUMNS: &'static str = \"\\n[ItemNo]\\n,[Unit Price]\\n,[1stPrice]\\n\";\n"
+    ));
+}
+
+#[test]
+fn test_make_struct_writes_descriptions() {
+    let mut table_list =
+        resolve_tables(vec![make_table("ritem", &[("ItemNo", "int", "NO")])], false);
+    table_list[0].description = Some("Menu items".into());
+    table_list[0].columns[0].description = Some("Item number,\r\nassigned by the POS.".into());
+
+    let file = unparse_items(make_struct(
+        "JkrtdbDboRitem",
+        "jkrtdb.dbo.ritem",
+        &table_list[0],
+    ));
+    let file = format_rust_file(std::path::Path::new("structs.rs"), &file).unwrap();
+    assert!(file.starts_with("/// Menu items\n#[allow(non_snake_case, non_camel_case_types)]\n"));
+    assert!(
+        file.contains("    /// Item number,\n    /// assigned by the POS.\n    pub ItemNo: i32,\n")
+    );
+}
+
+#[test]
+fn test_make_try_from_row_and_crud_query() {
+    let mut table_list = resolve_tables(
+        vec![make_table(
+            "ritem",
+            &[
+                ("ItemNo", "int", "NO"),
+                ("Item Name", "nvarchar", "YES"),
+                ("Price", "decimal", "NO"),
+                ("CreatedAt", "datetime", "YES"),
+            ],
+        )],
+        false,
+    );
+    table_list[0].primary_keys = vec!["ItemNo".into()];
+    table_list[0].columns[0].is_identity = true;
+
+    let file = unparse_items(make_crud_query(&table_list[0], "JkrtdbDboRitem"));
+    assert!(file.contains("    pub async fn select_by_key<S>(\n        client: &mut tiberius::Client<S>,\n        key_ItemNo: i32,\n    ) -> tiberius::Result<Option<Self>>\n"));
+    assert!(file.contains("tiberius::Query::new(\n            \"UPDATE [jkrtdb].[dbo].[ritem] SET [Item Name] = @P1, [Price] = @P2, [CreatedAt] = @P3 WHERE [ItemNo] = @P4\",\n        );\n"));
+    assert!(file.contains("        query.bind(self.Price.clone());\n        query.bind(self.CreatedAt);\n        query.bind(self.ItemNo);\n"));
+    assert!(file.contains("INSERT INTO [jkrtdb].[dbo].[ritem] ([Item Name], [Price], [CreatedAt]) VALUES (@P1, @P2, @P3)"));
+    assert!(file.contains(" WHEN NOT MATCHED THEN INSERT ([Item Name], [Price], [CreatedAt]) VALUES (source.[Item Name], source.[Price], source.[CreatedAt]);"));
+    assert!(file.contains("        for chunk in rows.chunks(500) {\n"));
+
+    let file = unparse_items(make_try_from_row(&table_list[0], "JkrtdbDboRitem"));
+    assert!(file.contains("impl TryFrom<&tiberius::Row> for JkrtdbDboRitem {"));
+    assert!(file.contains("            Item_Name: row\n                .try_get::<&str, _>(\"Item Name\")?\n                .map(|value| value.to_string()),\n"));
+    assert!(file.contains(
+        "            ItemNo: row\n                .try_get::<i32, _>(\"ItemNo\")?\n                .ok_or_else(|| tiberius::error::Error::Conversion(\n                    \"ItemNo is NULL\".into(),\n                ))?,\n"
+    ));
+}
+
+#[test]
+fn test_make_upsert_query() {
+    let mut table_list = resolve_tables(
+        vec![
+            make_table(
+                "rtag",
+                &[("TagNo", "int", "NO"), ("Name", "nvarchar", "NO")],
+            ),
+            make_table(
+                "ritem",
+                &[("ItemNo", "int", "NO"), ("Price", "decimal", "NO")],
+            ),
+            make_table("rsequence", &[("SequenceNo", "int", "NO")]),
+        ],
+        false,
+    );
+    table_list[0].primary_keys = vec!["TagNo".into(), "Name".into()];
+    table_list[1].primary_keys = vec!["ItemNo".into()];
+    table_list[1].columns[0].is_identity = true;
+    table_list[2].primary_keys = vec!["SequenceNo".into()];
+    table_list[2].columns[0].is_identity = true;
+
+    // A table of only key columns inserts the missing keys and updates nothing.
+    let file = unparse_items(make_crud_query(&table_list[0], "JkrtdbDboRtag"));
+    assert!(file.contains("\"MERGE [jkrtdb].[dbo].[rtag] WITH (HOLDLOCK) AS target USING (SELECT @P1 AS [TagNo], @P2 AS [Name]) AS source ON target.[TagNo] = source.[TagNo] AND target.[Name] = source.[Name] WHEN NOT MATCHED THEN INSERT ([TagNo], [Name]) VALUES (source.[TagNo], source.[Name]);\""));
+    assert!(!file.contains("WHEN MATCHED"));
+    format_rust_file(std::path::Path::new("rtag.rs"), &file).unwrap();
+
+    // An identity key is matched on but left out of the insert.
+    let file = unparse_items(make_crud_query(&table_list[1], "JkrtdbDboRitem"));
+    assert!(file.contains("\"MERGE [jkrtdb].[dbo].[ritem] WITH (HOLDLOCK) AS target USING (SELECT @P1 AS [ItemNo], @P2 AS [Price]) AS source ON target.[ItemNo] = source.[ItemNo] WHEN MATCHED THEN UPDATE SET target.[Price] = source.[Price] WHEN NOT MATCHED THEN INSERT ([Price]) VALUES (source.[Price]);\""));
+    assert!(file.contains("        for chunk in rows.chunks(1000) {\n"));
+    assert!(file
+        .contains("                        .map(|column| format!(\"@P{}\", row * 2 + column))\n"));
+    assert!(file.contains("                    \"MERGE [jkrtdb].[dbo].[ritem] WITH (HOLDLOCK) AS target USING (VALUES \",\n                    &values,\n                    \") AS source ([ItemNo], [Price]) ON target.[ItemNo] = source.[ItemNo] WHEN MATCHED"));
+    assert!(file.contains(
+        "                query.bind(row.ItemNo);\n                query.bind(row.Price.clone());\n"
+    ));
+    format_rust_file(std::path::Path::new("ritem.rs"), &file).unwrap();
+
+    let file = unparse_items(make_crud_query(&table_list[2], "JkrtdbDboRsequence"));
+    assert!(file.contains("pub async fn delete_by_key<S>("));
+    assert!(!file.contains("MERGE"));
+    assert!(!file.contains("upsert"));
+
+    // A row of more parameters than a statement allows is not chunked.
+    let column_names = (0..2101)
+        .map(|index| format!("Column{}", index))
+        .collect::<Vec<String>>();
+    let columns = column_names
+        .iter()
+        .map(|column_name| (column_name.as_str(), "int", "NO"))
+        .collect::<Vec<(&str, &str, &str)>>();
+    let mut table_list = resolve_tables(vec![make_table("rwide", &columns)], false);
+    table_list[0].primary_keys = vec!["Column0".into()];
+    let file = unparse_items(make_crud_query(&table_list[0], "JkrtdbDboRwide"));
+    assert!(file.contains("pub async fn upsert<S>("));
+    assert!(!file.contains("upsert_many"));
+
+    table_list[0].columns.pop();
+    let file = unparse_items(make_crud_query(&table_list[0], "JkrtdbDboRwide"));
+    assert!(file.contains("        for chunk in rows.chunks(1) {\n"));
+}
+
+#[test]
+fn test_make_crud_query_binds_bytes() {
+    let mut table_list = resolve_tables(
+        vec![make_table(
+            "rfile",
+            &[
+                ("FileNo", "int", "NO"),
+                ("Data", "varbinary", "YES"),
+                ("Hash", "binary", "NO"),
+                ("Version", "timestamp", "NO"),
+            ],
+        )],
+        false,
+    );
+    table_list[0].primary_keys = vec!["FileNo".into()];
+
+    let file = unparse_items(make_struct(
+        "JkrtdbDboRfile",
+        "jkrtdb.dbo.rfile",
+        &table_list[0],
+    ));
+    assert!(file.contains("    pub Data: Option<Vec<u8>>,\n"));
+    assert!(file.contains("    pub Version: Vec<u8>,\n"));
+
+    let file = unparse_items(make_crud_query(&table_list[0], "JkrtdbDboRfile"));
+    assert!(file.contains(
+        "INSERT INTO [jkrtdb].[dbo].[rfile] ([FileNo], [Data], [Hash]) VALUES (@P1, @P2, @P3)"
+    ));
+    assert!(file.contains(
+        "UPDATE [jkrtdb].[dbo].[rfile] SET [Data] = @P1, [Hash] = @P2 WHERE [FileNo] = @P3"
+    ));
+    assert!(file.contains(
+        "        query.bind(self.FileNo);\n        query.bind(self.Data.clone());\n        query.bind(self.Hash.clone());\n"
+    ));
+    format_rust_file(std::path::Path::new("rfile.rs"), &file).unwrap();
+}
+
+#[test]
+fn test_make_crud_query_prefixes_key_arguments() {
+    let mut table_list = vec![make_table(
+        "rclient",
+        &[("Client", "int", "NO"), ("Query", "nvarchar", "NO")],
+    )];
+    table_list[0].primary_keys = vec!["Client".into(), "Query".into()];
+    let naming: crate::helpers::structs::NamingConfig =
+        toml::from_str("field_case = \"snake_case\"").unwrap();
+    crate::helpers::resolve_names::resolve_names(&mut table_list, &naming, false, &[]).unwrap();
+
+    let file = unparse_items(make_crud_query(&table_list[0], "JkrtdbDboRclient"));
+    assert!(file.contains(
+        "        client: &mut tiberius::Client<S>,\n        key_client: i32,\n        key_query: String,\n    ) -> tiberius::Result<u64>\n"
+    ));
+    assert!(file.contains("        query.bind(key_client);\n        query.bind(key_query);\n"));
+    format_rust_file(std::path::Path::new("rclient.rs"), &file).unwrap();
+}
+
+#[test]
+fn test_get_row_reader_reads_bytes_and_xml() {
+    for data_type in ["binary", "varbinary", "image", "timestamp", "rowversion"] {
+        assert_eq!(get_rust_type(data_type), "Vec<u8>");
+        assert_eq!(
+            get_row_reader(data_type),
+            ("&[u8]", "|value| value.to_vec()")
+        );
     }
-    Ok(())
+    assert_eq!(get_rust_type("xml"), "String");
+    assert_eq!(
+        get_row_reader("xml"),
+        ("&tiberius::xml::XmlData", "|value| value.to_string()")
+    );
 }
 
-pub async fn rs_one_file_writer(
-    path: &Option<String>,
-    table_list: &Vec<Table>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let result = File::create(match path {
-        Some(path) => PathBuf::from_str(path.as_str())?,
-        None => env::current_dir()?.join(STRUCT_FILE_NAME),
-    })
-    .await?;
-    let mut writer: tokio::io::BufWriter<File> = tokio::io::BufWriter::new(result);
-    let mut file: String = "\n".into();
+#[test]
+fn test_make_bulk_insert() {
+    let mut table_list = resolve_tables(
+        vec![make_table(
+            "ritem",
+            &[
+                ("Price", "decimal", "NO"),
+                ("ItemNo", "int", "NO"),
+                ("CreatedAt", "datetime", "YES"),
+                ("Total", "decimal", "YES"),
+                ("ItemName", "nvarchar", "YES"),
+            ],
+        )],
+        false,
+    );
+    for (column, ordinal_position) in table_list[0].columns.iter_mut().zip([4, 1, 3, 5, 2]) {
+        column.ordinal_position = ordinal_position;
+        column.numeric_scale = Some(2);
+    }
+    table_list[0].columns[1].is_identity = true;
+    table_list[0].columns[3].is_computed = true;
 
-    let mut use_insert_query = false;
-    for table in table_list {
-        let (table_name, _file_name, sql_table_name) = get_table_names(table);
-        file.push_str(make_struct(table_name.as_str(), sql_table_name.as_str(), table).as_str());
-        file.push_str(&make_columns(sql_table_name.as_str(), table));
+    table_list[0].use_bulk_insert = true;
+    let file = unparse_items(make_bulk_insert(&table_list[0], "JkrtdbDboRitem").unwrap());
+    assert!(file.contains(
+        "        row.push(tiberius::IntoSql::into_sql(self.ItemName));\n        row.push(tiberius::ColumnData::DateTime(self.CreatedAt.map(to_sql_datetime)));\n        row.push(\n            tiberius::ColumnData::Numeric(\n                Some(self.Price)\n                    .map(|value| parse_sql_value(\"Price\", to_sql_numeric(&value, 2)))\n                    .transpose()?,\n            ),\n        );\n        Ok(row)\n"
+    ));
+    assert!(file.contains("            request.send(row.try_into_row()?).await?;\n"));
+    assert!(file.contains(
+        "        let mut request = client.bulk_insert(\"[jkrtdb].[dbo].[ritem]\").await?;\n"
+    ));
+    let helpers = unparse_items(import_bulk_helpers(&[&table_list[0]]));
+    assert!(helpers.contains("fn parse_sql_value<T, E: std::fmt::Display>("));
+    assert!(helpers.contains("fn to_sql_datetime("));
+    assert!(helpers.contains("fn to_sql_numeric("));
+    assert!(!helpers.contains("fn to_sql_small_datetime("));
+    format_rust_file(
+        std::path::Path::new("ritem.rs"),
+        &format!("{}{}", file, helpers),
+    )
+    .unwrap();
 
-        if table.use_signal_parser {
-            file.push_str(&make_signal_parser(table, &table_name, &table_name));
-        }
+    table_list[0].columns[0].data_type = "money".into();
+    let error = make_bulk_insert(&table_list[0], "JkrtdbDboRitem").unwrap_err();
+    assert!(error.to_string().starts_with(
+        "use_bulk_insert of jkrtdb.dbo.ritem: column Price (money) can not be bulk loaded"
+    ));
+}
+
+#[test]
+fn test_make_lookup_enum() {
+    let mut table = make_table("rreference", &[("Code", "int", "NO")]);
+    table.lookup_enum = Some(
+        serde_json::from_value(serde_json::json!({
+            "key": "Code",
+            "name": "Label",
+            "enum_name": null,
+            "values": [
+                { "KEY": "1", "NAME": "Cash" },
+                { "KEY": "2", "NAME": "credit card" },
+                { "KEY": "3", "NAME": "Cash" },
+                { "KEY": "4", "NAME": null },
+                { "KEY": "-5", "NAME": "Credit-Card" },
+                { "KEY": "6", "NAME": "CASH" },
+            ],
+        }))
+        .unwrap(),
+    );
+    let mut table_list = resolve_tables(vec![table], false);
+    let table = &mut table_list[0];
+    let lookup_enum = table.lookup_enum.clone().unwrap();
+
+    let file = unparse_items(make_lookup_enum(table, &lookup_enum).unwrap());
+    assert_eq!(table.idents.enum_name, "JkrtdbDboRreferenceEnum");
+    assert!(file.contains("#[serde(try_from = \"i32\", into = \"i32\")]\npub enum JkrtdbDboRreferenceEnum {\n    Cash,\n    /// credit card\n    CreditCard,\n    /// Cash\n    Cash2,\n    /// 4\n    Value4,\n    /// Credit-Card\n    CreditCard2,\n    /// CASH\n    Cash3,\n}\n"));
+    assert!(file.contains("            2 => Ok(Self::CreditCard),\n            3 => Ok(Self::Cash2),\n            4 => Ok(Self::Value4),\n            -5 => Ok(Self::CreditCard2),\n"));
+    assert!(file.contains("            JkrtdbDboRreferenceEnum::Value4 => 4,\n"));
+    format_rust_file(std::path::Path::new("rreference.rs"), &file).unwrap();
+
+    table.columns[0].data_type = "nvarchar".into();
+    let file = unparse_items(make_lookup_enum(table, &lookup_enum).unwrap());
+    assert!(file.contains("        match value.as_str() {\n            \"1\" => Ok(Self::Cash),\n"));
+    assert!(file.contains("            JkrtdbDboRreferenceEnum::Cash => \"1\".into(),\n"));
+
+    // Keys out of the range of the key type are an error.
+    table.columns[0].data_type = "tinyint".into();
+    let error = make_lookup_enum(table, &lookup_enum).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "enums key \"-5\" of JkrtdbDboRreferenceEnum is not an u8 value"
+    );
+
+    table.columns[0].data_type = "datetime".into();
+    assert!(make_lookup_enum(table, &lookup_enum).is_err());
+}
 
-        if table.use_insert_query {
-            use_insert_query = true;
+#[test]
+fn test_make_rs_split_files_writes_ascii_modules() {
+    let mut table_list = vec![
+        make_table("가격", &[("ItemNo", "int", "NO")]),
+        make_table("ritem", &[("ItemNo", "int", "NO")]),
+    ];
+    let mut naming: crate::helpers::structs::NamingConfig =
+        toml::from_str("unicode = \"keep\"").unwrap();
+    naming.dictionary.insert("ritem".into(), "품목".into());
+    crate::helpers::resolve_names::resolve_names(&mut table_list, &naming, true, &[]).unwrap();
+
+    let file_list = RustGenerator
+        .generate(
+            &table_list,
+            &GeneratorOptions {
+                path: Some("sample".into()),
+                use_split_file: true,
+                use_reexport: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        file_list[&PathBuf::from("sample/mod.rs")],
+        "pub mod jkrtdb_dbo_gagyeog;\npub mod jkrtdb_dbo_pummog;\npub use jkrtdb_dbo_gagyeog::JkrtdbDbo가격;\npub use jkrtdb_dbo_pummog::JkrtdbDbo품목;\n"
+    );
+    assert!(file_list.contains_key(&PathBuf::from("sample/jkrtdb_dbo_gagyeog.rs")));
+    assert!(file_list[&PathBuf::from("sample/jkrtdb_dbo_pummog.rs")]
+        .contains("pub struct JkrtdbDbo품목 {"));
+}
+
+/// Serde structs and the optional tiberius impls of every table.
+pub struct RustGenerator;
+
+impl CodeGenerator for RustGenerator {
+    fn name(&self) -> &'static str {
+        "rust"
+    }
+
+    fn generate(
+        &self,
+        table_list: &[Table],
+        options: &GeneratorOptions,
+    ) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+        let mut file_list = match options.use_split_file {
+            true => make_rs_split_files(
+                &options.path,
+                options.use_reexport,
+                table_list,
+                &options.split_directorys,
+            )?,
+            false => make_rs_one_file(&options.path, table_list)?,
+        };
+        for (path, file) in file_list.iter_mut() {
+            *file = format_rust_file(path, file)?;
         }
+        Ok(file_list)
     }
-    file.pop();
+}
 
-    file = format!("{}{}", import_file(&file, use_insert_query), file);
+fn make_rs_one_file(
+    path: &Option<String>,
+    table_list: &[Table],
+) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+    let path = get_output_path(path, STRUCT_FILE_NAME)?;
+    let mut items = vec![];
+    for table in table_list {
+        let (table_name, _file_name, sql_table_name) = get_table_names(table);
+        items.extend(make_table_items(table, &table_name, &sql_table_name)?.0);
+    }
 
-    writer.write_all(file.as_bytes()).await?;
-    writer.flush().await?;
-    Ok(())
+    let table_list = table_list.iter().collect::<Vec<&Table>>();
+    let use_insert_query = table_list.iter().any(|table| table.use_insert_query);
+    let imports = import_file(&table_list, use_insert_query);
+    let helpers = import_bulk_helpers(&table_list);
+    let file = unparse_items(quote! {
+        #imports
+        #(#items)*
+        #helpers
+    });
+    Ok(HashMap::from([(path, file)]))
 }
 
-pub async fn rs_split_file_writer(
+fn make_rs_split_files(
     path: &Option<String>,
-    table_list: &Vec<Table>,
-    split_directorys: &Vec<SplitDirectoryConfig>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let path = match path {
-        Some(path) => PathBuf::from_str(path.as_str())?,
-        None => env::current_dir()?.join("sample"),
-    };
+    use_reexport: bool,
+    table_list: &[Table],
+    split_directorys: &[SplitDirectoryConfig],
+) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+    let path = get_output_path(path, "sample")?;
     let mut file_list: HashMap<PathBuf, String> = HashMap::new();
-    let mut mod_list: HashMap<String, Vec<String>> = HashMap::new();
+    let mut module_list: Vec<(PathBuf, Vec<String>)> = vec![];
 
     for table in table_list {
-        let mut file: String = "\n".into();
         let (table_name, file_name, sql_table_name) = get_table_names(table);
+        let (items, enum_name) = make_table_items(table, &table_name, &sql_table_name)?;
+        let imports = import_file(&[table], table.use_insert_query);
+        let helpers = import_bulk_helpers(&[table]);
+        let file = unparse_items(quote! {
+            #imports
+            #(#items)*
+            #helpers
+        });
 
-        file.push_str(&make_struct(
-            table_name.as_str(),
-            sql_table_name.as_str(),
-            table,
-        ));
-        file.push_str(&make_columns(sql_table_name.as_str(), table));
+        let mut names = vec![table_name];
+        names.extend(enum_name);
+        let current_path =
+            get_split_file_path(&path, table, split_directorys, &format!("{}.rs", file_name));
+        module_list.push((current_path.clone(), names));
+        file_list.insert(current_path, file);
+    }
+    file_list.extend(make_mod_files(&path, &module_list, use_reexport));
+    Ok(file_list)
+}
+
+/// Returns the items of a table and the name of its lookup enum.
+fn make_table_items(
+    table: &Table,
+    table_name: &str,
+    sql_table_name: &str,
+) -> Result<(Vec<TokenStream>, Option<String>), Box<dyn std::error::Error>> {
+    let mut items = vec![
+        make_struct(table_name, sql_table_name, table),
+        make_columns(sql_table_name, table),
+    ];
+
+    if table.use_try_from_row || table.use_crud_query {
+        items.push(make_try_from_row(table, table_name));
+    }
+
+    if table.use_crud_query {
+        items.push(make_crud_query(table, table_name));
+    }
+
+    if table.use_bulk_insert {
+        items.push(make_bulk_insert(table, table_name)?);
+    }
+
+    if table.use_signal_parser {
+        items.push(make_signal_parser(table, table_name, table_name));
+    }
+
+    let mut enum_name = None;
+    if let Some(lookup_enum) = &table.lookup_enum {
+        items.push(make_lookup_enum(table, lookup_enum)?);
+        enum_name = Some(table.idents.enum_name.clone());
+    }
+    Ok((items, enum_name))
+}
+
+/// Enum of the rows of a lookup table with `TryFrom` and `Into` of the key type, serde
+/// reads and writes the key. The enum and variant names are resolved by `resolve_names`.
+fn make_lookup_enum(
+    table: &Table,
+    lookup_enum: &LookupEnum,
+) -> Result<TokenStream, Box<dyn std::error::Error>> {
+    let enum_name = &table.idents.enum_name;
+    let key_column = table
+        .columns
+        .iter()
+        .find(|column| column.column_name == lookup_enum.key)
+        .ok_or_else(|| {
+            format!(
+                "enums key {} is not a column of {}",
+                lookup_enum.key,
+                table.name.get_sql_table_name()
+            )
+        })?;
+    let key_type = match get_rust_type(&key_column.data_type) {
+        key_type @ ("u8" | "i16" | "i32" | "i64" | "String") => key_type,
+        _ => Err(format!(
+            "enums key {} of {} is {}, not an integer or text",
+            lookup_enum.key,
+            table.name.get_sql_table_name(),
+            key_column.data_type
+        ))?,
+    };
 
-        if table.use_signal_parser {
-            file.push_str(&make_signal_parser(table, &table_name, &table_name));
+    let mut keys: HashSet<&str> = HashSet::new();
+    let mut variants: Vec<(Ident, TokenStream, &str)> = vec![];
+    for value in &lookup_enum.values {
+        let Some(key) = &value.key else {
+            continue;
+        };
+        if !keys.insert(key) {
+            Err(format!(
+                "enums key {:?} of {} is not unique",
+                key, enum_name
+            ))?;
         }
-        file.pop();
-
-        file = format!("{}{}", import_file(&file, table.use_insert_query), file);
-
-        let current_path = match split_directorys.is_empty() {
-            true => path.join(format!("{}.rs", file_name)),
-            false => {
-                let mut current_path = path.clone();
-                for split_directory in split_directorys {
-                    if file_name.starts_with(&split_directory.starts_with_name) {
-                        current_path =
-                            current_path.join(split_directory.directory_name.copy_string());
-                        match mod_list.get_mut(current_path.to_str().unwrap()) {
-                            Some(mod_list) => {
-                                mod_list.push(file_name.copy_string());
-                            }
-                            None => {
-                                mod_list.insert(
-                                    current_path.to_str().unwrap().into(),
-                                    vec![file_name.copy_string()],
-                                );
-                            }
-                        }
-                        break;
-                    }
+        let key_literal = match key_type {
+            "String" => quote! { #key },
+            _ => {
+                let key = key
+                    .trim()
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|key| match key_type {
+                        "u8" => u8::try_from(*key).is_ok(),
+                        "i16" => i16::try_from(*key).is_ok(),
+                        "i32" => i32::try_from(*key).is_ok(),
+                        _ => true,
+                    })
+                    .ok_or_else(|| {
+                        format!(
+                            "enums key {:?} of {} is not an {} value",
+                            key, enum_name, key_type
+                        )
+                    })?;
+                let literal = Literal::u64_unsuffixed(key.unsigned_abs());
+                match key < 0 {
+                    true => quote! { -#literal },
+                    false => quote! { #literal },
                 }
-                current_path.join(format!("{}.rs", file_name))
             }
         };
-        file_list.insert(current_path, file);
+        variants.push((
+            make_ident(&value.variant_name),
+            key_literal,
+            value.name.as_deref().unwrap_or(key),
+        ));
     }
-    write_mod_files(mod_list).await?;
-    write_files(file_list).await?;
 
-    Ok(())
+    let enum_ident = make_ident(enum_name);
+    let key_ty = make_type(key_type);
+    let enum_docs = make_doc_attrs(&Some(format!(
+        "Rows of {} by {}.",
+        table.name.get_sql_table_name(),
+        lookup_enum.key
+    )));
+    let enum_variants = variants.iter().map(|(variant_ident, _, label)| {
+        let variant_docs = match variant_ident == label {
+            true => vec![],
+            false => make_doc_attrs(&Some(label.to_string())),
+        };
+        quote! {
+            #(#variant_docs)*
+            #variant_ident
+        }
+    });
+    let matched_value = match key_type {
+        "String" => quote! { value.as_str() },
+        _ => quote! { value },
+    };
+    let try_from_arms = variants.iter().map(|(variant_ident, key_literal, _)| {
+        quote! { #key_literal => Ok(Self::#variant_ident), }
+    });
+    let error_message = format!("{{:?}} is not a key of {}", enum_name);
+    let into_key = match key_type {
+        "String" => quote! { .into() },
+        _ => quote! {},
+    };
+    let from_arms = variants.iter().map(|(variant_ident, key_literal, _)| {
+        quote! { #enum_ident::#variant_ident => #key_literal #into_key, }
+    });
+    Ok(quote! {
+        #(#enum_docs)*
+        #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[serde(try_from = #key_type, into = #key_type)]
+        pub enum #enum_ident {
+            #(#enum_variants),*
+        }
+
+        impl TryFrom<#key_ty> for #enum_ident {
+            type Error = String;
+
+            fn try_from(value: #key_ty) -> Result<Self, Self::Error> {
+                match #matched_value {
+                    #(#try_from_arms)*
+                    _ => Err(format!(#error_message, value)),
+                }
+            }
+        }
+
+        impl From<#enum_ident> for #key_ty {
+            fn from(value: #enum_ident) -> Self {
+                match value {
+                    #(#from_arms)*
+                }
+            }
+        }
+    })
 }
 
-async fn write_mod_files(
-    mod_list: HashMap<String, Vec<String>>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let mut file_list: HashMap<PathBuf, String> = HashMap::new();
-    for (path, file_names) in mod_list {
-        let path = PathBuf::from_str(path.as_str())?;
-        let mut file: String = "".into();
-        for file_name in file_names {
-            file.push_str(&format!("pub mod {};\n", file_name));
+fn make_signal_parser(table: &Table, table_name: &str, table_name_dart: &str) -> TokenStream {
+    let struct_ident = make_ident(table_name);
+    let dart_ident = make_ident(table_name_dart);
+    let fields = table.columns.iter().map(|column| {
+        let field_ident = make_ident(&column.field_name);
+        let value = match (
+            column.data_type.as_str(),
+            column.is_nullable.as_str() == "YES",
+        ) {
+            ("datetime", true) => quote! {
+                match &self.#field_ident {
+                    Some(value) => Some(value.to_rfc3339()),
+                    None => None,
+                }
+            },
+            ("datetime", false) => quote! { self.#field_ident.to_rfc3339() },
+            _ => quote! { self.#field_ident },
+        };
+        quote! { #field_ident: #value }
+    });
+    quote! {
+        impl #struct_ident {
+            pub fn to_dart(self) -> crate::signals::#dart_ident {
+                crate::signals::#dart_ident {
+                    #(#fields),*
+                }
+            }
         }
-        file_list.insert(path.join("mod.rs"), file);
     }
-    write_files(file_list).await?;
-    Ok(())
 }
 
-fn make_signal_parser(table: &Table, table_name: &str, table_name_dart: &str) -> String {
-    let mut file: String = "".into();
-    file.push_str(&format!("impl {} ", table_name));
-    file.push_str("{\n");
+fn make_try_from_row(table: &Table, table_name: &str) -> TokenStream {
+    let struct_ident = make_ident(table_name);
+    let fields = table.columns.iter().map(|column| {
+        let (read_type, convert) = get_row_reader(column.data_type.as_str());
+        let read_type = make_type(read_type);
+        let column_name = &column.column_name;
+        let mut value = quote! { row.try_get::<#read_type, _>(#column_name)? };
+        if !convert.is_empty() {
+            let convert: syn::Expr = syn::parse_str(convert).expect("row conversion");
+            value = quote! { #value.map(#convert) };
+        }
+        if column.is_nullable.as_str() != "YES" {
+            let message = format!("{} is NULL", column.column_name);
+            value = quote! {
+                #value.ok_or_else(|| tiberius::error::Error::Conversion(#message.into()))?
+            };
+        }
+        let field_ident = make_ident(&column.field_name);
+        quote! { #field_ident: #value }
+    });
+    quote! {
+        impl TryFrom<&tiberius::Row> for #struct_ident {
+            type Error = tiberius::error::Error;
 
-    file.push_str(&format!(
-        "    pub fn to_dart(self) -> crate::signals::{} {}",
-        &table_name_dart, "{\n"
-    ));
-    file.push_str(&format!(
-        "        crate::signals::{} {}",
-        &table_name_dart, "{\n"
+            fn try_from(row: &tiberius::Row) -> Result<Self, Self::Error> {
+                Ok(Self { #(#fields),* })
+            }
+        }
+    }
+}
+
+fn make_crud_query(table: &Table, table_name: &str) -> TokenStream {
+    let struct_ident = make_ident(table_name);
+    let sql_table_name = get_quoted_sql_table_name(table);
+    let columns = table
+        .columns
+        .iter()
+        .map(|column| quote_sql_identifier(&column.column_name))
+        .collect::<Vec<String>>();
+    let key_columns = table
+        .primary_keys
+        .iter()
+        .filter_map(|key| {
+            table
+                .columns
+                .iter()
+                .find(|column| &column.column_name == key)
+        })
+        .collect::<Vec<&ColumnName>>();
+    let insert_columns = table
+        .columns
+        .iter()
+        .filter(|column| !column.is_server_generated())
+        .collect::<Vec<&ColumnName>>();
+    let value_columns = insert_columns
+        .iter()
+        .filter(|column| !table.primary_keys.contains(&column.column_name))
+        .copied()
+        .collect::<Vec<&ColumnName>>();
+    let use_key = table.name.table_type == "BASE TABLE"
+        && !key_columns.is_empty()
+        && key_columns.len() == table.primary_keys.len();
+
+    let mut fns = vec![];
+    let select_query = format!("SELECT {} FROM {}", columns.join(", "), sql_table_name);
+    fns.push(make_client_fn(
+        "select_all",
+        quote! {},
+        quote! {},
+        quote! { Vec<Self> },
+        &select_query,
+        &[],
+        quote! {
+            let rows = query.query(client).await?.into_first_result().await?;
+            rows.iter().map(Self::try_from).collect()
+        },
     ));
-    for column in &table.columns {
-        let column_name = get_column_name(column);
-        let data_type = match column.data_type.as_str() {
-            "datetime" => ".to_rfc3339()",
-            _ => "",
-        };
 
-        file.push_str(&format!(
-            "            {}: {},\n",
-            &column_name,
-            match column.is_nullable.as_str() == "YES" {
-                true => match column.data_type.as_str() {
-                    "datetime" => make_matcher(
-                        &format!("Some(Into::into(&*value{}))", data_type),
-                        &column_name
-                    ),
-                    _ => format!("self.{}{}", column_name, data_type),
-                },
-                false => format!("self.{}{}", column_name, data_type),
-            }
+    if table.name.table_type == "BASE TABLE" && !insert_columns.is_empty() {
+        let insert_query = format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            sql_table_name,
+            quote_sql_identifiers(&insert_columns).join(", "),
+            make_params(insert_columns.len(), 0).join(", ")
+        );
+        fns.push(make_client_fn(
+            "insert",
+            quote! { &self, },
+            quote! {},
+            quote! { u64 },
+            &insert_query,
+            &make_binds(&insert_columns, Some(quote! { self })),
+            quote! { Ok(query.execute(client).await?.total()) },
+        ));
+    }
+
+    if use_key {
+        let key_args = key_columns.iter().map(|column| {
+            let key_ident = get_key_ident(column);
+            let field_type = make_type(&get_field_type(column));
+            quote! { , #key_ident: #field_type }
+        });
+        let key_args = quote! { #(#key_args)* };
+        let key_where = make_where(&key_columns, 0);
+
+        fns.push(make_client_fn(
+            "select_by_key",
+            quote! {},
+            key_args.clone(),
+            quote! { Option<Self> },
+            &format!("{} WHERE {}", select_query, key_where),
+            &make_binds(&key_columns, None),
+            quote! {
+                let row = query.query(client).await?.into_row().await?;
+                row.as_ref().map(Self::try_from).transpose()
+            },
+        ));
+
+        if !value_columns.is_empty() {
+            let params = make_params(value_columns.len(), 0);
+            let update_query = format!(
+                "UPDATE {} SET {} WHERE {}",
+                sql_table_name,
+                value_columns
+                    .iter()
+                    .zip(params.iter())
+                    .map(|(column, param)| format!(
+                        "{} = {}",
+                        quote_sql_identifier(&column.column_name),
+                        param
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                make_where(&key_columns, value_columns.len())
+            );
+            let mut binds = make_binds(&value_columns, Some(quote! { self }));
+            binds.extend(make_binds(&key_columns, Some(quote! { self })));
+            fns.push(make_client_fn(
+                "update_by_key",
+                quote! { &self, },
+                quote! {},
+                quote! { u64 },
+                &update_query,
+                &binds,
+                quote! { Ok(query.execute(client).await?.total()) },
+            ));
+        }
+
+        // MERGE needs a WHEN clause, a table of only server generated columns has none.
+        if !insert_columns.is_empty() {
+            fns.push(make_upsert_query(
+                &sql_table_name,
+                &key_columns,
+                &insert_columns,
+                &value_columns,
+            ));
+        }
+
+        fns.push(make_client_fn(
+            "delete_by_key",
+            quote! {},
+            key_args,
+            quote! { u64 },
+            &format!("DELETE FROM {} WHERE {}", sql_table_name, key_where),
+            &make_binds(&key_columns, None),
+            quote! { Ok(query.execute(client).await?.total()) },
         ));
     }
-    file.push_str("        }\n");
-    file.push_str("    }\n");
-    file.push_str("}\n\n");
-    file
+    quote! {
+        #[allow(non_snake_case)]
+        impl #struct_ident {
+            #(#fns)*
+        }
+    }
 }
 
-fn make_matcher(some_text: &str, column_name: &str) -> String {
-    format!(
-        "match &self.{} {}{}{}{}",
-        &column_name,
-        "{\n",
-        "                Some(value) => ",
-        some_text,
-        ",\n                None => None,\n            }"
-    )
+/// MERGE with `WITH (HOLDLOCK)` so concurrent upserts of one key do not both insert.
+fn make_upsert_query(
+    sql_table_name: &str,
+    key_columns: &[&ColumnName],
+    insert_columns: &[&ColumnName],
+    value_columns: &[&ColumnName],
+) -> TokenStream {
+    let mut source_columns = key_columns.to_vec();
+    source_columns.extend(value_columns);
+    let source_names = quote_sql_identifiers(&source_columns);
+    let on = quote_sql_identifiers(key_columns)
+        .iter()
+        .map(|name| format!("target.{0} = source.{0}", name))
+        .collect::<Vec<String>>()
+        .join(" AND ");
+    let mut merge_end = String::new();
+    if !value_columns.is_empty() {
+        merge_end.push_str(&format!(
+            " WHEN MATCHED THEN UPDATE SET {}",
+            quote_sql_identifiers(value_columns)
+                .iter()
+                .map(|name| format!("target.{0} = source.{0}", name))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+    if !insert_columns.is_empty() {
+        let insert_names = quote_sql_identifiers(insert_columns);
+        merge_end.push_str(&format!(
+            " WHEN NOT MATCHED THEN INSERT ({}) VALUES ({})",
+            insert_names.join(", "),
+            insert_names
+                .iter()
+                .map(|name| format!("source.{}", name))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+    merge_end.push(';');
+    let merge_start = format!("MERGE {} WITH (HOLDLOCK) AS target USING ", sql_table_name);
+
+    let upsert = make_client_fn(
+        "upsert",
+        quote! { &self, },
+        quote! {},
+        quote! { u64 },
+        &format!(
+            "{}(SELECT {}) AS source ON {}{}",
+            merge_start,
+            source_names
+                .iter()
+                .zip(make_params(source_names.len(), 0))
+                .map(|(name, param)| format!("{} AS {}", param, name))
+                .collect::<Vec<String>>()
+                .join(", "),
+            on,
+            merge_end
+        ),
+        &make_binds(&source_columns, Some(quote! { self })),
+        quote! { Ok(query.execute(client).await?.total()) },
+    );
+
+    // SQL Server allows 2100 parameters and 1000 rows of VALUES in one statement, a row of
+    // more columns can not be sent in chunks.
+    if source_columns.len() > 2100 {
+        return upsert;
+    }
+    let chunk_size = Literal::usize_unsuffixed((2000 / source_columns.len()).clamp(1, 1000));
+    let column_count = Literal::usize_unsuffixed(source_columns.len());
+    let values_start = format!("{}(VALUES ", merge_start);
+    let values_end = format!(
+        ") AS source ({}) ON {}{}",
+        source_names.join(", "),
+        on,
+        merge_end
+    );
+    let binds = make_binds(&source_columns, Some(quote! { row }));
+    quote! {
+        #upsert
+
+        pub async fn upsert_many<S>(client: &mut tiberius::Client<S>, rows: &[Self]) -> tiberius::Result<u64>
+        where
+            S: futures_util::AsyncRead + futures_util::AsyncWrite + Unpin + Send,
+        {
+            let mut total = 0;
+            for chunk in rows.chunks(#chunk_size) {
+                let values = (0..chunk.len())
+                    .map(|row| {
+                        let params = (1..=#column_count)
+                            .map(|column| format!("@P{}", row * #column_count + column))
+                            .collect::<Vec<String>>();
+                        format!("({})", params.join(", "))
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                let mut query = tiberius::Query::new([#values_start, &values, #values_end].concat());
+                for row in chunk {
+                    #(query.bind(#binds);)*
+                }
+                total += query.execute(&mut *client).await?.total();
+            }
+            Ok(total)
+        }
+    }
 }
 
-fn make_struct(table_name: &str, sql_table_name: &str, table: &Table) -> String {
-    let mut file = String::new();
-    file.push_str("#[allow(non_snake_case, non_camel_case_types)]\n");
+/// `try_into_row` pushes the columns in `ordinal_position` order without server generated
+/// columns, the same columns `Client::bulk_insert` reads from the table.
+fn make_bulk_insert(
+    table: &Table,
+    table_name: &str,
+) -> Result<TokenStream, Box<dyn std::error::Error>> {
+    if table.name.table_type != "BASE TABLE" {
+        return Ok(TokenStream::new());
+    }
+    let columns = get_bulk_columns(table);
 
-    if !table.use_insert_query {
-        file.push_str("#[derive(Serialize, Deserialize, Debug, Clone)]\n");
-    } else {
-        file.push_str(
-            "#[derive(Serialize, Deserialize, InsertQuery, TableSchema, Debug, Clone)]\n",
-        );
-        file.push_str(&format!("#[table_name = \"{}\"]\n", sql_table_name));
-    }
-    file.push_str(&format!("pub struct {} {{\n", table_name));
-    for column in &table.columns {
-        let column_name = get_column_name(column);
-        if column.data_type == "datetime" {
-            match column.is_nullable.as_str() {
-                "YES" => file.push_str("    #[serde(with = \"ts_seconds_option\")]\n"),
-                "NO" => file.push_str("    #[serde(with = \"ts_seconds\")]\n"),
-                _ => {}
-            };
+    let mut row_writers = vec![];
+    for column in &columns {
+        // `INSERT BULK` of tiberius writes column names without brackets.
+        let is_plain_name = column
+            .column_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !column.column_name.starts_with(|c: char| c.is_ascii_digit());
+        match get_row_writer(column) {
+            Some(row_writer) if is_plain_name => row_writers.push(row_writer),
+            _ => Err(format!(
+                "use_bulk_insert of {}: column {} ({}) can not be bulk loaded, set use_bulk_insert = false for this table",
+                table.name.get_sql_table_name(),
+                column.column_name,
+                column.data_type
+            ))?,
         }
+    }
 
-        let data_type = match column.data_type.as_str() {
-            "bit" => "bool",
-            "tinyint" => "u8",
-            "smallint" => "i16",
-            "int" => "i32",
-            "bigint" => "i64",
-            "real" => "f32",
-            "float" => "f64",
-            "money" => "f64",
-            "datetime" => "DateTime<Utc>",
-            "binary" => "Vec<u8>",
-            "image" => "Vec<u8>",
-            "ntext" => "String",
-            "nvarchar" => "String",
-            "text" => "String",
-            _ => "String",
-        };
+    let struct_ident = make_ident(table_name);
+    let row_mut = (!row_writers.is_empty()).then(|| quote! { mut });
+    let sql_table_name = get_quoted_sql_table_name(table);
+    Ok(quote! {
+        impl #struct_ident {
+            pub fn try_into_row(self) -> tiberius::Result<tiberius::TokenRow<'static>> {
+                let #row_mut row = tiberius::TokenRow::new();
+                #(row.push(#row_writers);)*
+                Ok(row)
+            }
 
-        file.push_str(&format!(
-            "    pub {}: {},\n",
-            column_name,
-            match column.is_nullable.as_str() == "YES" {
-                true => format!("Option<{}>", data_type),
-                false => data_type.into(),
+            pub async fn bulk_insert<S>(client: &mut tiberius::Client<S>, rows: impl IntoIterator<Item = Self>) -> tiberius::Result<u64>
+            where
+                S: futures_util::AsyncRead + futures_util::AsyncWrite + Unpin + Send,
+            {
+                let mut request = client.bulk_insert(#sql_table_name).await?;
+                for row in rows {
+                    request.send(row.try_into_row()?).await?;
+                }
+                Ok(request.finalize().await?.total())
             }
-        ));
-    }
-    file.push_str("}\n\n");
-    file
+        }
+    })
 }
 
-fn make_columns(table_name: &str, table: &Table) -> String {
-    let mut file = String::new();
-    let table_name_uppercase = table.name.table_name.to_uppercase();
-    file.push_str(&format!(
-        "pub const {}_TABLE_NAME: &'static str = \"{}\";\n\n",
-        table_name_uppercase, table_name
-    ));
-    file.push_str(&format!(
-        "pub const {}_COLUMNS: &'static str = \"\n",
-        table_name_uppercase
-    ));
-    let mut index = 0;
-    for column in &table.columns {
-        let column_name = get_column_name(column);
+/// Columns of `try_into_row` in `ordinal_position` order.
+fn get_bulk_columns(table: &Table) -> Vec<&ColumnName> {
+    let mut columns = table
+        .columns
+        .iter()
+        .filter(|column| !column.is_server_generated())
+        .collect::<Vec<&ColumnName>>();
+    columns.sort_by_key(|column| column.ordinal_position);
+    columns
+}
 
-        if index != 0 {
-            file.push_str(",");
+/// Returns the `ColumnData` of a field, `None` when bulk load can not encode the type.
+/// Text parsed into a sql type returns a `Conversion` error naming the column.
+fn get_row_writer(column: &ColumnName) -> Option<TokenStream> {
+    let field_ident = make_ident(&column.field_name);
+    let value = quote! { self.#field_ident };
+    let option = match column.is_nullable.as_str() == "YES" {
+        true => value.clone(),
+        false => quote! { Some(#value) },
+    };
+    let column_name = &column.column_name;
+    let parse = |parser: TokenStream| {
+        quote! { #option.map(|value| parse_sql_value(#column_name, #parser)).transpose()? }
+    };
+    let row_writer = match column.data_type.as_str() {
+        "bit" | "tinyint" | "smallint" | "int" | "bigint" | "real" | "float" | "char"
+        | "varchar" | "nchar" | "nvarchar" | "binary" | "varbinary" => {
+            quote! { tiberius::IntoSql::into_sql(#value) }
+        }
+        "xml" => quote! {
+            tiberius::IntoSql::into_sql(#option.map(tiberius::xml::XmlData::new))
+        },
+        "datetime" => quote! {
+            tiberius::ColumnData::DateTime(#option.map(to_sql_datetime))
+        },
+        "smalldatetime" => {
+            let value = parse(quote! { to_sql_small_datetime(&value) });
+            quote! { tiberius::ColumnData::SmallDateTime(#value) }
+        }
+        "datetime2" => {
+            let value = parse(quote! {
+                tiberius::time::chrono::NaiveDateTime::parse_from_str(&value, "%Y-%m-%d %H:%M:%S%.f")
+            });
+            quote! { tiberius::IntoSql::into_sql(#value) }
+        }
+        "datetimeoffset" => {
+            let value =
+                parse(quote! { tiberius::time::chrono::DateTime::parse_from_rfc3339(&value) });
+            quote! { tiberius::IntoSql::into_sql(#value) }
+        }
+        "date" => {
+            let value = parse(quote! { value.parse::<tiberius::time::chrono::NaiveDate>() });
+            quote! { tiberius::IntoSql::into_sql(#value) }
+        }
+        "time" => {
+            let value = parse(quote! { value.parse::<tiberius::time::chrono::NaiveTime>() });
+            quote! { tiberius::IntoSql::into_sql(#value) }
+        }
+        "decimal" | "numeric" => {
+            let scale = Literal::i32_unsuffixed(column.numeric_scale.unwrap_or_default());
+            let value = parse(quote! { to_sql_numeric(&value, #scale) });
+            quote! { tiberius::ColumnData::Numeric(#value) }
         }
-        file.push_str(&format!("[{}]\n", column_name,));
-        index += 1;
+        "uniqueidentifier" => {
+            let value = parse(quote! { value.parse::<tiberius::Uuid>() });
+            quote! { tiberius::ColumnData::Guid(#value) }
+        }
+        _ => return None,
+    };
+    Some(row_writer)
+}
+
+/// Returns the conversion functions used by `try_into_row` of the tables.
+fn import_bulk_helpers(table_list: &[&Table]) -> TokenStream {
+    let data_types = table_list
+        .iter()
+        .filter(|table| table.use_bulk_insert && table.name.table_type == "BASE TABLE")
+        .flat_map(|table| get_bulk_columns(table))
+        .map(|column| column.data_type.as_str())
+        .collect::<HashSet<&str>>();
+    let uses = |types: &[&str]| types.iter().any(|data_type| data_types.contains(data_type));
+
+    let mut helpers = vec![];
+    if uses(&[
+        "smalldatetime",
+        "datetime2",
+        "datetimeoffset",
+        "date",
+        "time",
+        "decimal",
+        "numeric",
+        "uniqueidentifier",
+    ]) {
+        helpers.push(quote! {
+            fn parse_sql_value<T, E: std::fmt::Display>(column_name: &str, value: Result<T, E>) -> tiberius::Result<T> {
+                value.map_err(|error| tiberius::error::Error::Conversion(format!("{}: {}", column_name, error).into()))
+            }
+        });
     }
-    file.push_str("\";\n\n");
-    file
+    if uses(&["datetime"]) {
+        helpers.push(quote! {
+            fn to_sql_datetime(value: tiberius::time::chrono::DateTime<tiberius::time::chrono::Utc>) -> tiberius::time::DateTime {
+                let value = value.naive_utc();
+                let days = value.date() - tiberius::time::chrono::NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+                let time = value.time() - tiberius::time::chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+                tiberius::time::DateTime::new(days.num_days() as i32, (time.num_milliseconds() * 3 / 10) as u32)
+            }
+        });
+    }
+    if uses(&["smalldatetime"]) {
+        helpers.push(quote! {
+            fn to_sql_small_datetime(value: &str) -> Result<tiberius::time::SmallDateTime, Box<dyn std::error::Error>> {
+                let value = tiberius::time::chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")?;
+                let days = value.date() - tiberius::time::chrono::NaiveDate::from_ymd_opt(1900, 1, 1).unwrap();
+                let time = value.time() - tiberius::time::chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+                Ok(tiberius::time::SmallDateTime::new(days.num_days() as u16, time.num_minutes() as u16))
+            }
+        });
+    }
+    if uses(&["decimal", "numeric"]) {
+        helpers.push(quote! {
+            fn to_sql_numeric(value: &str, scale: u8) -> Result<tiberius::numeric::Numeric, std::num::ParseIntError> {
+                let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+                let fraction = format!("{:0<1$}", fraction, scale as usize);
+                let fraction = fraction.get(..scale as usize).unwrap_or(&fraction);
+                let value = format!("{}{}", integer, fraction).parse::<i128>()?;
+                Ok(tiberius::numeric::Numeric::new_with_scale(value, scale))
+            }
+        });
+    }
+    quote! { #(#helpers)* }
+}
+
+/// `receiver` (`&self,`) is written before `client`, `args` (`, name: type`..) after it.
+fn make_client_fn(
+    fn_name: &str,
+    receiver: TokenStream,
+    args: TokenStream,
+    return_type: TokenStream,
+    sql: &str,
+    binds: &[TokenStream],
+    body: TokenStream,
+) -> TokenStream {
+    let fn_ident = make_ident(fn_name);
+    let query_mut = (!binds.is_empty()).then(|| quote! { mut });
+    quote! {
+        pub async fn #fn_ident<S>(#receiver client: &mut tiberius::Client<S> #args) -> tiberius::Result<#return_type>
+        where
+            S: futures_util::AsyncRead + futures_util::AsyncWrite + Unpin + Send,
+        {
+            let #query_mut query = tiberius::Query::new(#sql);
+            #(query.bind(#binds);)*
+            #body
+        }
+    }
+}
+
+/// Returns `@P1`, `@P2`.. parameter names starting after `offset`.
+fn make_params(count: usize, offset: usize) -> Vec<String> {
+    (1..=count)
+        .map(|index| format!("@P{}", index + offset))
+        .collect()
+}
+
+fn make_where(key_columns: &[&ColumnName], offset: usize) -> String {
+    key_columns
+        .iter()
+        .zip(make_params(key_columns.len(), offset))
+        .map(|(column, param)| format!("{} = {}", quote_sql_identifier(&column.column_name), param))
+        .collect::<Vec<String>>()
+        .join(" AND ")
 }
 
-fn import_file(file: &str, use_insert_query: bool) -> String {
-    let mut import_file = String::new();
+/// Fields of `owner` (`self`, `row`) or the key arguments without an owner, text and bytes
+/// of an owner are cloned.
+fn make_binds(columns: &[&ColumnName], owner: Option<TokenStream>) -> Vec<TokenStream> {
+    columns
+        .iter()
+        .map(|column| {
+            let field_ident = make_ident(&column.field_name);
+            match (&owner, get_rust_type(column.data_type.as_str())) {
+                (Some(owner), "String" | "Vec<u8>") => quote! { #owner.#field_ident.clone() },
+                (Some(owner), _) => quote! { #owner.#field_ident },
+                (None, _) => {
+                    let key_ident = get_key_ident(column);
+                    quote! { #key_ident }
+                }
+            }
+        })
+        .collect()
+}
+
+/// Argument of a key column, `key_` keeps it apart from `client`, `query` and `row`.
+fn get_key_ident(column: &ColumnName) -> Ident {
+    make_ident(&format!(
+        "key_{}",
+        column.field_name.trim_start_matches("r#")
+    ))
+}
+
+fn quote_sql_identifiers(columns: &[&ColumnName]) -> Vec<String> {
+    columns
+        .iter()
+        .map(|column| quote_sql_identifier(&column.column_name))
+        .collect()
+}
 
-    import_file.push_str("use serde::{Deserialize, Serialize};\n");
-    if file.contains("#[serde(with = \"ts_seconds_option\")]")
-        || file.contains("#[serde(with = \"ts_seconds\")]")
-    {
-        if file.contains("#[serde(with = \"ts_seconds_option\")]") {
-            import_file.push_str("use chrono::serde::ts_seconds_option;\n");
+fn get_quoted_sql_table_name(table: &Table) -> String {
+    format!(
+        "{}.{}.{}",
+        quote_sql_identifier(&table.name.table_catalog),
+        quote_sql_identifier(&table.name.table_schema),
+        quote_sql_identifier(&table.name.table_name)
+    )
+}
+
+/// Returns the type read by `Row::try_get` and the conversion to the field type.
+fn get_row_reader(data_type: &str) -> (&'static str, &'static str) {
+    match data_type {
+        "bit" => ("bool", ""),
+        "tinyint" => ("u8", ""),
+        "smallint" => ("i16", ""),
+        "int" => ("i32", ""),
+        "bigint" => ("i64", ""),
+        "real" => ("f32", ""),
+        "float" => ("f64", ""),
+        "money" => ("f64", ""),
+        "smallmoney" => ("f64", "|value| value.to_string()"),
+        "datetime" => (
+            "tiberius::time::chrono::NaiveDateTime",
+            "|value| value.and_utc()",
+        ),
+        "smalldatetime" | "datetime2" => (
+            "tiberius::time::chrono::NaiveDateTime",
+            "|value| value.to_string()",
+        ),
+        "datetimeoffset" => (
+            "tiberius::time::chrono::DateTime<tiberius::time::chrono::FixedOffset>",
+            "|value| value.to_rfc3339()",
+        ),
+        "date" => (
+            "tiberius::time::chrono::NaiveDate",
+            "|value| value.to_string()",
+        ),
+        "time" => (
+            "tiberius::time::chrono::NaiveTime",
+            "|value| value.to_string()",
+        ),
+        "decimal" | "numeric" => ("tiberius::numeric::Numeric", "|value| value.to_string()"),
+        "uniqueidentifier" => ("tiberius::Uuid", "|value| value.to_string()"),
+        "binary" | "varbinary" | "image" | "timestamp" | "rowversion" => {
+            ("&[u8]", "|value| value.to_vec()")
         }
-        if file.contains("#[serde(with = \"ts_seconds\")]") {
-            import_file.push_str("use chrono::serde::ts_seconds;\n");
+        "xml" => ("&tiberius::xml::XmlData", "|value| value.to_string()"),
+        _ => ("&str", "|value| value.to_string()"),
+    }
+}
+
+fn make_struct(table_name: &str, sql_table_name: &str, table: &Table) -> TokenStream {
+    let struct_ident = make_ident(table_name);
+    let struct_docs = make_doc_attrs(&table.description);
+    let derive = match table.use_insert_query {
+        false => quote! { #[derive(Serialize, Deserialize, Debug, Clone)] },
+        true => quote! {
+            #[derive(Serialize, Deserialize, InsertQuery, TableSchema, Debug, Clone)]
+            #[table_name = #sql_table_name]
+        },
+    };
+    let fields = table.columns.iter().map(|column| {
+        let field_docs = make_doc_attrs(&column.description);
+        let column_name = &column.column_name;
+        let rename = (column.field_name.trim_start_matches("r#") != column_name)
+            .then(|| quote! { #[serde(rename = #column_name)] });
+        let with = match (column.data_type.as_str(), column.is_nullable.as_str()) {
+            ("datetime", "YES") => Some(quote! { #[serde(with = "ts_seconds_option")] }),
+            ("datetime", "NO") => Some(quote! { #[serde(with = "ts_seconds")] }),
+            _ => None,
+        };
+        let field_ident = make_ident(&column.field_name);
+        let field_type = make_type(&get_field_type(column));
+        quote! {
+            #(#field_docs)*
+            #rename
+            #with
+            pub #field_ident: #field_type
         }
+    });
+    quote! {
+        #(#struct_docs)*
+        #[allow(non_snake_case, non_camel_case_types)]
+        #derive
+        pub struct #struct_ident {
+            #(#fields),*
+        }
+    }
+}
 
-        if !use_insert_query {
-            import_file.push_str("use tiberius::time::chrono::{DateTime, Utc};\n");
-        } else {
-            import_file.push_str("use table_schema_derive::{InsertQuery, TableSchema};\n");
-            import_file.push_str("use table_schema_traits::{InsertQuery, TableSchema};\n");
-            import_file.push_str("use tiberius::{\n");
-            import_file.push_str("    time::chrono::{DateTime, Utc},\n");
-            import_file.push_str("    ToSql,\n");
-            import_file.push_str("};\n");
+fn make_columns(table_name: &str, table: &Table) -> TokenStream {
+    let table_name_ident = make_ident(&format!("{}_TABLE_NAME", table.idents.const_name));
+    let columns_ident = make_ident(&format!("{}_COLUMNS", table.idents.const_name));
+    let mut columns = String::from("\n");
+    for (index, column) in table.columns.iter().enumerate() {
+        if index != 0 {
+            columns.push(',');
+        }
+        columns.push_str(&quote_sql_identifier(&column.column_name));
+        columns.push('\n');
+    }
+    quote! {
+        pub const #table_name_ident: &'static str = #table_name;
+
+        pub const #columns_ident: &'static str = #columns;
+    }
+}
+
+/// `use` lines of the tables, chrono serde modules only for datetime columns.
+fn import_file(table_list: &[&Table], use_insert_query: bool) -> TokenStream {
+    let has_datetime = |is_nullable: &str| {
+        table_list.iter().any(|table| {
+            table
+                .columns
+                .iter()
+                .any(|column| column.data_type == "datetime" && column.is_nullable == is_nullable)
+        })
+    };
+    let ts_seconds_option =
+        has_datetime("YES").then(|| quote! { use chrono::serde::ts_seconds_option; });
+    let ts_seconds = has_datetime("NO").then(|| quote! { use chrono::serde::ts_seconds; });
+    let datetime = (has_datetime("YES") || has_datetime("NO")).then(|| {
+        quote! { use tiberius::time::chrono::{DateTime, Utc}; }
+    });
+    let insert_query = use_insert_query.then(|| {
+        quote! {
+            use table_schema_derive::{InsertQuery, TableSchema};
+            use table_schema_traits::{InsertQuery, TableSchema};
+            use tiberius::ToSql;
         }
+    });
+    quote! {
+        use serde::{Deserialize, Serialize};
+        #ts_seconds_option
+        #ts_seconds
+        #datetime
+        #insert_query
     }
-    import_file
 }
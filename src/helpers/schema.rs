@@ -0,0 +1,243 @@
+use std::{collections::HashMap, path::Path, path::PathBuf};
+
+#[cfg(feature = "connect")]
+use crate::helpers::auth_handler::get_tables;
+#[cfg(test)]
+use crate::helpers::fixtures::make_table;
+use crate::helpers::{
+    generators::{generate_files, get_code_generators},
+    resolve_names::resolve_names,
+    structs::{generators_default, GeneratorOptions, InnerArgs, NamingConfig, Table, TableOptions},
+};
+
+#[test]
+fn test_schema_snapshot() {
+    let mut table = make_table("ritem", &[("ItemNo", "int", "NO")]);
+    table.primary_keys = vec!["ItemNo".into()];
+    let snapshot_path = std::env::temp_dir().join("mssql_tiberius_schema_parser_snapshot.json");
+    Schema::from_tables(vec![
+        table,
+        make_table("rstore", &[("StoreNo", "int", "NO")]),
+    ])
+    .save_snapshot(&snapshot_path)
+    .unwrap();
+
+    let file_list = Schema::from_snapshot(&snapshot_path)
+        .unwrap()
+        .filter(|table| table.name.table_schema == "dbo")
+        .generators(["rust"])
+        .options(GeneratorOptions {
+            path: Some("structs.rs".into()),
+            ..Default::default()
+        })
+        .generate()
+        .unwrap();
+
+    assert_eq!(file_list.len(), 1);
+    let file = &file_list[&PathBuf::from("structs.rs")];
+    assert!(file.contains("pub struct JkrtdbDboRitem {"));
+    assert!(file.contains("pub struct JkrtdbDboRstore {"));
+    assert!(!file.contains("pub async fn select_all<S>("));
+
+    // The table options of the config replace the ones saved in the snapshot.
+    let args: InnerArgs = toml::from_str(
+        "user = \"sa\"\npassword = \"\"\ntype = \"ServerAuth\"\npath = \"structs.rs\"\ngenerators = [\"rust\"]\n\n[database]\nuse_import_special = true\ntables = [{ table_name = \"jkrtdb_dbo_ritem\", use_crud_query = true, use_insert_query = false }]\n",
+    )
+    .unwrap();
+    let file_list = Schema::from_args(args)
+        .unwrap()
+        .load_snapshot(&snapshot_path)
+        .unwrap()
+        .generate()
+        .unwrap();
+    std::fs::remove_file(&snapshot_path).unwrap();
+
+    let file = &file_list[&PathBuf::from("structs.rs")];
+    assert!(file.contains("pub async fn select_all<S>("));
+    assert!(
+        !file.contains("#[derive(Serialize, Deserialize, InsertQuery, TableSchema, Debug, Clone)]")
+    );
+    assert!(!file.contains("JkrtdbDboRstore"));
+}
+
+/// Tables of a server or a snapshot, and the options to generate files from them.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// use mssql_tiberius_schema_parser::{GeneratorOptions, Schema};
+///
+/// let file_list = Schema::from_snapshot("schema.json")?
+///     .filter(|table| table.name.table_catalog == "jkrtdb")
+///     .generators(["rust"])
+///     .options(GeneratorOptions {
+///         path: Some("src/structs.rs".into()),
+///         ..Default::default()
+///     })
+///     .generate()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Schema {
+    table_list: Vec<Table>,
+    naming: NamingConfig,
+    generators: Vec<String>,
+    options: GeneratorOptions,
+    /// Table options of `from_args`, applied again to the tables of a snapshot.
+    table_options: Option<TableOptions>,
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Schema {
+            table_list: vec![],
+            naming: NamingConfig::default(),
+            generators: generators_default(),
+            options: GeneratorOptions::default(),
+            table_options: None,
+        }
+    }
+}
+
+impl Schema {
+    /// Connects with `args` and reads the tables, the other options of `args` are kept
+    /// for `generate`.
+    #[cfg(feature = "connect")]
+    pub async fn connect(args: InnerArgs) -> Result<Self, Box<dyn std::error::Error>> {
+        get_code_generators(&args.generators)?;
+        let table_list = get_tables(&args).await?;
+        Ok(Schema::from_args(args)?.tables(table_list))
+    }
+
+    /// Takes the naming, generators and output options of `args` without connecting.
+    pub fn from_args(args: InnerArgs) -> Result<Self, Box<dyn std::error::Error>> {
+        get_code_generators(&args.generators)?;
+        let table_options = TableOptions::from_args(&args);
+        Ok(Schema {
+            table_list: vec![],
+            naming: args.naming.unwrap_or_default(),
+            generators: args.generators,
+            options: GeneratorOptions {
+                path: args.path,
+                signal_path: args.signal_path,
+                typescript_path: args.typescript_path,
+                dart_path: args.dart_path,
+                use_freezed: args.use_freezed,
+                proto_path: args.proto_path,
+                proto_package: args.proto_package,
+                proto_lock_path: args.proto_lock_path,
+                json_schema_path: args.json_schema_path,
+                csharp_path: args.csharp_path,
+                csharp_namespace: args.csharp_namespace,
+                use_csharp_record: args.use_csharp_record,
+                python_path: args.python_path,
+                use_python_dataclass: args.use_python_dataclass,
+                docs_path: args.docs_path,
+                docs_format: args.docs_format,
+                diagram_path: args.diagram_path,
+                diagram_formats: args.diagram_formats,
+                diagram_group: args.diagram_group,
+                diagram_tables: args.diagram_tables,
+                diagram_keys_only: args.diagram_keys_only,
+                use_split_file: args.use_split_file,
+                use_reexport: args.use_reexport,
+                split_directorys: args
+                    .database
+                    .and_then(|database| database.split_directory)
+                    .unwrap_or_default(),
+                template: args.template,
+            },
+            table_options: Some(table_options),
+        })
+    }
+
+    pub fn from_tables(table_list: Vec<Table>) -> Self {
+        Schema::default().tables(table_list)
+    }
+
+    /// Reads the tables written by `save_snapshot`.
+    pub fn from_snapshot(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        Schema::default().load_snapshot(path)
+    }
+
+    /// Reads the tables written by `save_snapshot`, with the table options of `from_args`
+    /// applied in place of the saved ones.
+    pub fn load_snapshot(self, path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let snapshot = std::fs::read_to_string(path)
+            .map_err(|error| format!("failed to read snapshot {:?}: {}", path, error))?;
+        let mut table_list = serde_json::from_str::<Vec<Table>>(&snapshot)
+            .map_err(|error| format!("failed to parse snapshot {:?}: {}", path, error))?;
+        if let Some(table_options) = &self.table_options {
+            table_list.retain(|table| table_options.is_imported(&table.name));
+            table_list
+                .iter_mut()
+                .for_each(|table| table_options.apply(table));
+        }
+        Ok(self.tables(table_list))
+    }
+
+    pub fn save_snapshot(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, serde_json::to_string_pretty(&self.table_list)?)?;
+        Ok(())
+    }
+
+    pub fn get_tables(&self) -> &[Table] {
+        &self.table_list
+    }
+
+    pub fn tables(mut self, table_list: Vec<Table>) -> Self {
+        self.table_list = table_list;
+        self.table_list
+            .sort_by_key(|table| table.name.get_config_name());
+        self
+    }
+
+    /// Keeps the tables `predicate` returns true for.
+    pub fn filter(mut self, predicate: impl Fn(&Table) -> bool) -> Self {
+        self.table_list.retain(|table| predicate(table));
+        self
+    }
+
+    pub fn naming(mut self, naming: NamingConfig) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Names of the generators to run, see `generators::get_registry`.
+    pub fn generators(mut self, names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.generators = names.into_iter().map(|name| name.into()).collect();
+        self
+    }
+
+    pub fn options(mut self, options: GeneratorOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns the generated files by path without writing them.
+    pub fn generate(&self) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+        let generators = get_code_generators(&self.generators)?;
+        let mut table_list = self.table_list.clone();
+        resolve_names(
+            &mut table_list,
+            &self.naming,
+            self.options.use_split_file,
+            &self.options.split_directorys,
+        )?;
+        generate_files(&generators, &table_list, &self.options)
+    }
+
+    /// Generates and writes the files, creating their directories.
+    pub fn write(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let mut file_list: Vec<(PathBuf, String)> = self.generate()?.into_iter().collect();
+        file_list.sort();
+        for (path, file) in file_list {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, file)?;
+        }
+        Ok(())
+    }
+}
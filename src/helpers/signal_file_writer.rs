@@ -1,178 +1,260 @@
-use std::{collections::HashMap, env, path::PathBuf, str::FromStr};
+use std::{collections::HashMap, path::PathBuf};
 
-use tokio::{fs::File, io::AsyncWriteExt};
+use proc_macro2::TokenStream;
+use quote::quote;
 
+#[cfg(test)]
+use crate::helpers::fixtures::{make_table, resolve_tables};
 use crate::helpers::{
-    common::convert_text_first_char_to_uppercase, config::STRUCT_SIGNAL_FILE_NAME, structs::Table,
+    common::{
+        convert_case, convert_text_first_char_to_uppercase, convert_unicode, escape_rust_keyword,
+        is_identifier_char,
+    },
+    config::STRUCT_SIGNAL_FILE_NAME,
+    structs::{GeneratorOptions, Table},
+    traits::code_generator::CodeGenerator,
 };
 
 use super::{
-    common::{get_table_names, write_files},
-    structs::{ColumnName, SplitDirectoryConfig},
-    traits::StringUtil,
+    common::{
+        format_rust_file, get_nullable_type, get_output_path, get_rust_type, get_split_file_path,
+        get_table_names, make_doc_attrs, make_ident, make_mod_files, make_type, unparse_items,
+    },
+    structs::{ColumnName, NamingConfig, SplitDirectoryConfig},
 };
 
-pub async fn signal_file_writer(
-    path: &Option<String>,
-    use_split_file: bool,
-    table_list: &Vec<Table>,
-    split_directorys: &Vec<SplitDirectoryConfig>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    match use_split_file {
-        true => {
-            signal_split_file_writer(path, table_list, split_directorys).await?;
-        }
-        false => {
-            signal_one_file_writer(path, table_list).await?;
-        }
-    }
-    Ok(())
+#[test]
+fn test_get_column_name_escapes_keywords() {
+    let column = |column_name: &str| -> ColumnName {
+        make_table("ritem", &[(column_name, "int", "NO")])
+            .columns
+            .remove(0)
+    };
+    let naming = NamingConfig::default();
+    assert_eq!(get_column_name(&column("self"), &naming), "Self_");
+    assert_eq!(get_column_name(&column("Type"), &naming), "Type");
+
+    let naming = NamingConfig {
+        keyword_suffix: Some("Field".into()),
+        ..Default::default()
+    };
+    assert_eq!(get_column_name(&column("self"), &naming), "SelfField");
+    assert_eq!(
+        escape_rust_keyword("type", &NamingConfig::default()),
+        "r#type"
+    );
+    assert_eq!(
+        escape_rust_keyword("crate", &NamingConfig::default()),
+        "crate_"
+    );
+
+    let naming: NamingConfig = toml::from_str("unicode = \"transliterate\"").unwrap();
+    let mut column = column("\u{301}\u{302}");
+    column.ordinal_position = 3;
+    assert_eq!(get_column_name(&column, &naming), "Column3");
 }
 
-pub async fn signal_one_file_writer(
-    path: &Option<String>,
-    table_list: &Vec<Table>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let result = File::create(match path {
-        Some(path) => PathBuf::from_str(path.as_str())?,
-        None => env::current_dir()?.join(STRUCT_SIGNAL_FILE_NAME),
-    })
-    .await?;
-    let mut writer = tokio::io::BufWriter::new(result);
-    let mut file: String = "use bincode::{Decode, Encode};\nuse rinf::{DartSignal, RustSignal, SignalPiece};\nuse serde::{Deserialize, Serialize};\n\n".into();
+#[test]
+fn test_make_signal_one_file() {
+    let mut table_list =
+        resolve_tables(vec![make_table("ritem", &[("ItemNo", "int", "NO")])], false);
+    table_list[0].use_signal_file = true;
 
-    for table in table_list {
-        if table.use_signal_file == false {
-            continue;
-        }
-        let (table_name, _, _) = get_table_names(table);
+    let file_list = SignalGenerator
+        .generate(
+            &table_list,
+            &GeneratorOptions {
+                signal_path: Some("signals.rs".into()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let file = &file_list[&PathBuf::from("signals.rs")];
+    assert!(file.starts_with("use bincode::{Decode, Encode};\nuse rinf::{DartSignal, RustSignal, SignalPiece};\nuse serde::{Deserialize, Serialize};\n"));
+    assert!(file.contains("pub struct JkrtdbDboRitemInput {}\n"));
+    assert!(file.contains(
+        "pub struct JkrtdbDboRitemOutput {\n    pub JkrtdbDboRitem: Vec<JkrtdbDboRitem>,\n}\n"
+    ));
+    assert!(file.contains("pub struct JkrtdbDboRitem {\n    pub ItemNo: i32,\n}\n"));
+}
 
-        file.push_str(&format!(
-            "#[derive(Deserialize, DartSignal, Debug, Clone)]\npub struct {}Input {}",
-            table_name, "{}\n\n"
-        ));
+/// rinf signal structs of the tables with `use_signal_file`.
+pub struct SignalGenerator;
 
-        file.push_str(&format!(
-            "#[allow(non_snake_case, non_camel_case_types)]\n#[derive(Serialize, RustSignal, Debug, Clone)]\npub struct {}Output {}",
-            table_name, "{\n"
-        ));
-        file.push_str(&format!("    pub {}: Vec<{}>,\n", table_name, table_name));
-        file.push_str("}\n\n");
+impl CodeGenerator for SignalGenerator {
+    fn name(&self) -> &'static str {
+        "signals"
+    }
 
-        file.push_str(make_struct(table_name.as_str(), table).as_str());
+    fn generate(
+        &self,
+        table_list: &[Table],
+        options: &GeneratorOptions,
+    ) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+        let mut file_list = match options.use_split_file {
+            true => make_signal_split_files(
+                &options.signal_path,
+                options.use_reexport,
+                table_list,
+                &options.split_directorys,
+            )?,
+            false => make_signal_one_file(&options.signal_path, table_list)?,
+        };
+        for (path, file) in file_list.iter_mut() {
+            *file = format_rust_file(path, file)?;
+        }
+        Ok(file_list)
     }
-    file.pop();
-    writer.write_all(file.as_bytes()).await?;
-    writer.flush().await?;
-    Ok(())
 }
 
-pub async fn signal_split_file_writer(
+fn make_signal_one_file(
     path: &Option<String>,
-    table_list: &Vec<Table>,
-    split_directorys: &Vec<SplitDirectoryConfig>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let path = match path {
-        Some(path) => PathBuf::from_str(path.as_str())?,
-        None => env::current_dir()?.join("sample"),
-    };
+    table_list: &[Table],
+) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+    let path = get_output_path(path, STRUCT_SIGNAL_FILE_NAME)?;
+    let items = table_list
+        .iter()
+        .filter(|table| table.use_signal_file)
+        .map(|table| {
+            let (table_name, _, _) = get_table_names(table);
+            make_signal_structs(&table_name, table)
+        });
+    let imports = import_signal_file();
+    let file = unparse_items(quote! {
+        #imports
+        #(#items)*
+    });
+    Ok(HashMap::from([(path, file)]))
+}
+
+fn make_signal_split_files(
+    path: &Option<String>,
+    use_reexport: bool,
+    table_list: &[Table],
+    split_directorys: &[SplitDirectoryConfig],
+) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+    let path = get_output_path(path, "sample")?;
     let mut file_list: HashMap<PathBuf, String> = HashMap::new();
+    let mut module_list: Vec<(PathBuf, Vec<String>)> = vec![];
 
     for table in table_list {
         if table.use_signal_file == false {
             continue;
         }
         let (table_name, file_name, _) = get_table_names(table);
-        let mut file: String = "use bincode::{Decode, Encode};\nuse rinf::{DartSignal, RustSignal, SignalPiece};\nuse serde::{Deserialize, Serialize};\n\n".into();
+        let imports = import_signal_file();
+        let items = make_signal_structs(&table_name, table);
+        let file = unparse_items(quote! {
+            #imports
+            #items
+        });
 
-        file.push_str(&format!(
-            "#[derive(Deserialize, DartSignal, Debug, Clone)]\npub struct {}Input {}",
-            table_name, "{}\n\n"
+        let current_path =
+            get_split_file_path(&path, table, split_directorys, &format!("{}.rs", file_name));
+        module_list.push((
+            current_path.clone(),
+            vec![
+                format!("{}Input", table_name),
+                format!("{}Output", table_name),
+                table_name,
+            ],
         ));
-
-        file.push_str(&format!(
-            "#[allow(non_snake_case, non_camel_case_types)]\n#[derive(Serialize, RustSignal, Debug, Clone)]\npub struct {}Output {}",
-            table_name, "{\n"
-        ));
-        file.push_str(&format!("    pub {}: Vec<{}>,\n", table_name, table_name));
-        file.push_str("}\n\n");
-
-        file.push_str(&make_struct(table_name.as_str(), table));
-        file.pop();
-
-        let current_path = match split_directorys.is_empty() {
-            true => path.join(format!("{}.rs", file_name)),
-            false => {
-                let mut current_path = path.clone();
-                for split_directory in split_directorys {
-                    if file_name.starts_with(&split_directory.starts_with_name) {
-                        current_path =
-                            current_path.join(split_directory.directory_name.copy_string());
-                        break;
-                    }
-                }
-                current_path.join(format!("{}.rs", file_name))
-            }
-        };
         file_list.insert(current_path, file);
     }
-    write_files(file_list).await?;
-    Ok(())
+    file_list.extend(make_mod_files(&path, &module_list, use_reexport));
+    Ok(file_list)
+}
+
+/// `use` lines of every signal file.
+fn import_signal_file() -> TokenStream {
+    quote! {
+        use bincode::{Decode, Encode};
+        use rinf::{DartSignal, RustSignal, SignalPiece};
+        use serde::{Deserialize, Serialize};
+    }
+}
+
+/// The `Input` and `Output` signals of a table and the struct they send.
+fn make_signal_structs(table_name: &str, table: &Table) -> TokenStream {
+    let struct_ident = make_ident(table_name);
+    let input_ident = make_ident(&format!("{}Input", table_name));
+    let output_ident = make_ident(&format!("{}Output", table_name));
+    let table_struct = make_struct(table_name, table);
+    quote! {
+        #[derive(Deserialize, DartSignal, Debug, Clone)]
+        pub struct #input_ident {}
+
+        #[allow(non_snake_case, non_camel_case_types)]
+        #[derive(Serialize, RustSignal, Debug, Clone)]
+        pub struct #output_ident {
+            pub #struct_ident: Vec<#struct_ident>,
+        }
+
+        #table_struct
+    }
 }
 
-fn make_struct(table_name: &str, table: &Table) -> String {
-    let mut file = String::new();
-    file.push_str("#[allow(non_snake_case, non_camel_case_types)]\n");
-    file.push_str("#[derive(Serialize, SignalPiece, Debug, Clone, Encode, Decode)]\n");
-    file.push_str(&format!("pub struct {} {{\n", table_name));
-    for column in &table.columns {
-        let column_name = get_column_name(column);
+fn make_struct(table_name: &str, table: &Table) -> TokenStream {
+    let struct_ident = make_ident(table_name);
+    let struct_docs = make_doc_attrs(&table.description);
+    let fields = table.columns.iter().map(|column| {
+        let field_docs = make_doc_attrs(&column.description);
 
+        // rinf signals send datetime as text.
         let data_type = match column.data_type.as_str() {
-            "bit" => "bool",
-            "tinyint" => "u8",
-            "smallint" => "i16",
-            "int" => "i32",
-            "bigint" => "i64",
-            "real" => "f32",
-            "float" => "f64",
-            "money" => "f64",
             "datetime" => "String",
-            "binary" => "Vec<u8>",
-            "image" => "Vec<u8>",
-            "ntext" => "String",
-            "nvarchar" => "String",
-            "text" => "String",
-            _ => "String",
+            data_type => get_rust_type(data_type),
         };
 
-        file.push_str(
-            format!(
-                "    pub {}: {},\n",
-                column_name,
-                match column.is_nullable.as_str() == "YES" {
-                    true => format!("Option<{}>", data_type),
-                    false => data_type.into(),
-                },
-            )
-            .as_str(),
-        );
+        let field_ident = make_ident(&column.field_name);
+        let field_type = make_type(&get_nullable_type(column, data_type));
+        quote! {
+            #(#field_docs)*
+            pub #field_ident: #field_type
+        }
+    });
+    quote! {
+        #(#struct_docs)*
+        #[allow(non_snake_case, non_camel_case_types)]
+        #[derive(Serialize, SignalPiece, Debug, Clone, Encode, Decode)]
+        pub struct #struct_ident {
+            #(#fields),*
+        }
     }
-    file.push_str("}\n\n");
-    file
 }
 
-pub fn get_column_name(column: &ColumnName) -> String {
-    match column
-        .column_name
-        .find(|c: char| !c.is_ascii_alphabetic() && !c.is_ascii_digit() && c != '_')
+pub fn get_column_name(column: &ColumnName, naming: &NamingConfig) -> String {
+    let column_ident = get_column_ident(column, naming);
+    let mut column_name = convert_case(&[column_ident.as_str()], &naming.field_case);
+    if column_name.is_empty() || column_name.starts_with(|c: char| c.is_ascii_digit()) {
+        column_name = column_ident;
+    }
+    escape_rust_keyword(
+        &format!(
+            "{}{}{}",
+            naming.field_prefix, column_name, naming.field_suffix
+        ),
+        naming,
+    )
+}
+
+fn get_column_ident(column: &ColumnName, naming: &NamingConfig) -> String {
+    let source_name = convert_unicode(&column.column_name, naming);
+    // Transliteration drops chars like combining marks, the position names the column then.
+    if source_name.is_empty() {
+        return format!("Column{}", column.ordinal_position);
+    }
+    match source_name
+        .find(|c: char| !is_identifier_char(c, naming))
         .is_some()
     {
         true => {
             let mut column_name = String::new();
 
-            column.column_name.chars().for_each(|c| {
-                let char_item = match !c.is_ascii_alphabetic() {
+            source_name.chars().for_each(|c| {
+                let char_item = match !c.is_ascii_alphabetic()
+                    && (c.is_ascii() || !is_identifier_char(c, naming))
+                {
                     true => "_".into(),
                     false => c.to_string(),
                 };
@@ -181,7 +263,7 @@ pub fn get_column_name(column: &ColumnName) -> String {
             convert_text_first_char_to_uppercase(column_name.as_str())
         }
         false => {
-            let mut column_name = column.column_name.clone();
+            let mut column_name = source_name;
             if column_name
                 .char_indices()
                 .next()
@@ -1,8 +1,93 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
+
 use serde::{Deserialize, Serialize};
 
-use crate::helpers::common::convert_text_first_char_to_uppercase;
+use crate::helpers::common::{convert_case, convert_file_name_chars, convert_identifier_chars};
+
+use super::args_parser::{AuthType, DiagramFormat, DiagramGroup, DocsFormat};
+
+#[test]
+fn test_naming_config() {
+    let table_name = TableName {
+        table_catalog: "jkrtdb".into(),
+        table_schema: "dbo".into(),
+        table_name: "ItemMaster".into(),
+        table_type: "BASE TABLE".into(),
+    };
+    let naming = NamingConfig::default();
+    assert_eq!(table_name.get_table_name(&naming), "JkrtdbDboItemMaster");
+    assert_eq!(table_name.get_file_name(&naming), "jkrtdb_dbo_itemmaster");
+    assert_eq!(table_name.get_const_name(&naming), "ITEMMASTER");
+
+    let naming = toml::from_str::<NamingConfig>(
+        r#"
+        struct_case = "PascalCase"
+        file_case = "snake_case"
+        const_case = "SCREAMING_SNAKE_CASE"
+        include_catalog = false
+        include_schema = "non_dbo"
+        struct_suffix = "Row"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(table_name.get_table_name(&naming), "ItemMasterRow");
+    assert_eq!(table_name.get_file_name(&naming), "item_master");
+    assert_eq!(table_name.get_const_name(&naming), "ITEM_MASTER");
+    assert_eq!(table_name.get_config_name(), "jkrtdb_dbo_itemmaster");
+
+    let table_name = TableName {
+        table_name: "1stItem".into(),
+        ..table_name
+    };
+    assert_eq!(table_name.get_file_name(&naming), "_1st_item");
+}
+
+#[test]
+fn test_unicode_file_names() {
+    let table_name = TableName {
+        table_catalog: "jkrtdb".into(),
+        table_schema: "dbo".into(),
+        table_name: "가격".into(),
+        table_type: "BASE TABLE".into(),
+    };
+    let naming = |unicode: &str| {
+        toml::from_str::<NamingConfig>(&format!("unicode = \"{}\"", unicode)).unwrap()
+    };
 
-use super::args_parser::AuthType;
+    // Struct names keep the chars, file and module names are always ASCII.
+    let keep = naming("keep");
+    assert_eq!(table_name.get_table_name(&keep), "JkrtdbDbo가격");
+    assert_eq!(table_name.get_file_name(&keep), "jkrtdb_dbo_gagyeog");
+    let transliterate = naming("transliterate");
+    assert_eq!(
+        table_name.get_table_name(&transliterate),
+        "JkrtdbDboGagyeog"
+    );
+    assert_eq!(
+        table_name.get_file_name(&transliterate),
+        "jkrtdb_dbo_gagyeog"
+    );
+    assert_eq!(
+        table_name.get_file_name(&naming("replace")),
+        "jkrtdb_dbo___"
+    );
+
+    let mut dictionary = naming("keep");
+    dictionary
+        .dictionary
+        .insert("가격".into(), "unit_price".into());
+    assert_eq!(
+        table_name.get_table_name(&dictionary),
+        "JkrtdbDboUnit_price"
+    );
+    assert_eq!(
+        table_name.get_file_name(&dictionary),
+        "jkrtdb_dbo_unit_price"
+    );
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -13,16 +98,43 @@ pub struct TableName {
     pub table_type: String,
 }
 
+impl ColumnName {
+    /// Identity, computed and rowversion columns are written by the server.
+    pub fn is_server_generated(&self) -> bool {
+        self.is_identity || self.is_computed || self.data_type == "timestamp"
+    }
+}
+
 impl TableName {
-    pub fn get_table_name(&self) -> String {
+    pub fn get_table_name(&self, naming: &NamingConfig) -> String {
         format!(
             "{}{}{}",
-            convert_text_first_char_to_uppercase(self.table_catalog.as_str()),
-            convert_text_first_char_to_uppercase(self.table_schema.as_str()),
-            convert_text_first_char_to_uppercase(self.table_name.as_str())
+            naming.struct_prefix,
+            convert_case(
+                &self.get_name_parts(naming, convert_identifier_chars),
+                &naming.struct_case
+            ),
+            naming.struct_suffix
         )
     }
-    pub fn get_file_name(&self) -> String {
+    /// File and module name, a leading digit gets a `_` prefix like field names.
+    pub fn get_file_name(&self, naming: &NamingConfig) -> String {
+        let file_name = format!(
+            "{}{}{}",
+            naming.file_prefix,
+            convert_case(
+                &self.get_name_parts(naming, convert_file_name_chars),
+                &naming.file_case
+            ),
+            naming.file_suffix
+        );
+        match file_name.is_empty() || file_name.starts_with(|c: char| c.is_ascii_digit()) {
+            true => format!("_{}", file_name),
+            false => file_name,
+        }
+    }
+    /// Name used to match `tables` and `split_directory` entries in config.toml.
+    pub fn get_config_name(&self) -> String {
         format!(
             "{}_{}_{}",
             self.table_catalog.to_lowercase(),
@@ -30,6 +142,32 @@ impl TableName {
             self.table_name.to_lowercase()
         )
     }
+    pub fn get_const_name(&self, naming: &NamingConfig) -> String {
+        convert_case(
+            &[convert_identifier_chars(&self.table_name, naming)],
+            &naming.const_case,
+        )
+    }
+    fn get_name_parts(
+        &self,
+        naming: &NamingConfig,
+        convert_chars: fn(&str, &NamingConfig) -> String,
+    ) -> Vec<String> {
+        let mut parts = vec![];
+        if naming.include_catalog {
+            parts.push(convert_chars(&self.table_catalog, naming));
+        }
+        let include_schema = match naming.include_schema {
+            IncludeSchema::Always => true,
+            IncludeSchema::Never => false,
+            IncludeSchema::NonDbo => !self.table_schema.eq_ignore_ascii_case("dbo"),
+        };
+        if include_schema {
+            parts.push(convert_chars(&self.table_schema, naming));
+        }
+        parts.push(convert_chars(&self.table_name, naming));
+        parts
+    }
     pub fn get_sql_table_name(&self) -> String {
         format!(
             "{}.{}.{}",
@@ -64,18 +202,134 @@ pub struct ColumnName {
     pub domain_catalog: Option<String>,
     pub domain_schema: Option<String>,
     pub domain_name: Option<String>,
+    #[serde(default)]
+    pub is_identity: bool,
+    #[serde(default)]
+    pub is_computed: bool,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Rust field name, resolved by `resolve_names`.
+    #[serde(skip)]
+    pub field_name: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct KeyColumn {
+    pub table_catalog: String,
+    pub table_schema: String,
+    pub table_name: String,
+    pub column_name: String,
+    pub ordinal_position: i32,
+}
+
+/// A column of a foreign key, one row per column of `sys.foreign_key_columns`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct ForeignKeyColumn {
+    pub constraint_name: String,
+    pub table_schema: String,
+    pub table_name: String,
+    pub column_name: String,
+    pub referenced_table_schema: String,
+    pub referenced_table_name: String,
+    pub referenced_column_name: String,
+    pub ordinal_position: i32,
+}
+
+/// A foreign key of a table, the columns are in key order. The referenced table is
+/// in the same database.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ForeignKey {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub referenced_table_schema: String,
+    pub referenced_table_name: String,
+    pub referenced_columns: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct ColumnProperty {
+    pub table_schema: String,
+    pub table_name: String,
+    pub column_name: String,
+    pub is_identity: bool,
+    pub is_computed: bool,
+}
+
+/// `MS_Description` extended property of a table, or of a column when `column_name`
+/// is set.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct DescriptionProperty {
+    pub table_schema: String,
+    pub table_name: String,
+    pub column_name: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Table {
     pub name: TableName,
     pub columns: Vec<ColumnName>,
+    /// Primary key column names in key order.
+    #[serde(default)]
+    pub primary_keys: Vec<String>,
+    #[serde(default)]
+    pub foreign_keys: Vec<ForeignKey>,
     #[serde(default = "use_signal_parser_default")]
     pub use_signal_parser: bool,
     #[serde(default = "use_signal_file_default")]
     pub use_signal_file: bool,
+    #[serde(default = "use_dart_file_default")]
+    pub use_dart_file: bool,
     #[serde(default = "use_insert_query_default")]
     pub use_insert_query: bool,
+    #[serde(default = "use_try_from_row_default")]
+    pub use_try_from_row: bool,
+    #[serde(default = "use_crud_query_default")]
+    pub use_crud_query: bool,
+    #[serde(default = "use_bulk_insert_default")]
+    pub use_bulk_insert: bool,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Rows of a lookup table of `[[enums]]`, written as a rust enum.
+    #[serde(default)]
+    pub lookup_enum: Option<LookupEnum>,
+    #[serde(skip)]
+    pub idents: TableIdents,
+}
+
+/// Key and name columns of a lookup table and its rows read at generation time.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LookupEnum {
+    pub key: String,
+    pub name: String,
+    pub enum_name: Option<String>,
+    #[serde(default)]
+    pub values: Vec<LookupEnumValue>,
+}
+
+/// A row of a lookup table, both columns are read as text.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct LookupEnumValue {
+    pub key: Option<String>,
+    pub name: Option<String>,
+    /// Rust variant name, resolved by `resolve_names`.
+    #[serde(skip)]
+    pub variant_name: String,
+}
+
+/// Unique generated names of a table, resolved by `resolve_names`.
+#[derive(Clone, Debug, Default)]
+pub struct TableIdents {
+    pub struct_name: String,
+    pub file_name: String,
+    pub const_name: String,
+    /// Name of the lookup enum, empty without one.
+    pub enum_name: String,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -93,11 +347,86 @@ pub struct InnerArgs {
     pub use_signal_parser: bool,
     #[serde(default = "use_split_file_default")]
     pub use_split_file: bool,
+    #[serde(default = "use_reexport_default")]
+    pub use_reexport: bool,
     #[serde(default = "use_insert_query_default")]
     pub use_insert_query: bool,
+    #[serde(default = "use_try_from_row_default")]
+    pub use_try_from_row: bool,
+    #[serde(default = "use_crud_query_default")]
+    pub use_crud_query: bool,
+    #[serde(default = "use_bulk_insert_default")]
+    pub use_bulk_insert: bool,
     pub path: Option<String>,
     pub signal_path: Option<String>,
+    pub typescript_path: Option<String>,
+    pub dart_path: Option<String>,
+    #[serde(default = "use_freezed_default")]
+    pub use_freezed: bool,
+    pub proto_path: Option<String>,
+    pub proto_package: Option<String>,
+    pub proto_lock_path: Option<String>,
+    pub json_schema_path: Option<String>,
+    pub csharp_path: Option<String>,
+    pub csharp_namespace: Option<String>,
+    #[serde(default = "use_csharp_record_default")]
+    pub use_csharp_record: bool,
+    pub python_path: Option<String>,
+    #[serde(default = "use_python_dataclass_default")]
+    pub use_python_dataclass: bool,
+    pub docs_path: Option<String>,
+    #[serde(default)]
+    pub docs_format: DocsFormat,
+    pub diagram_path: Option<String>,
+    #[serde(default = "diagram_formats_default")]
+    pub diagram_formats: Vec<DiagramFormat>,
+    #[serde(default)]
+    pub diagram_group: DiagramGroup,
+    pub diagram_tables: Option<Vec<String>>,
+    #[serde(default = "diagram_keys_only_default")]
+    pub diagram_keys_only: bool,
     pub database: Option<DatabaseConfig>,
+    pub naming: Option<NamingConfig>,
+    pub template: Option<TemplateConfig>,
+    pub enums: Option<Vec<EnumConfig>>,
+    #[serde(default = "generators_default")]
+    pub generators: Vec<String>,
+    pub save_snapshot: Option<String>,
+    pub load_snapshot: Option<String>,
+}
+
+pub fn generators_default() -> Vec<String> {
+    vec!["rust".into(), "signals".into(), "templates".into()]
+}
+
+/// Output options shared by every `CodeGenerator`.
+#[derive(Clone, Debug, Default)]
+pub struct GeneratorOptions {
+    pub path: Option<String>,
+    pub signal_path: Option<String>,
+    pub typescript_path: Option<String>,
+    pub dart_path: Option<String>,
+    pub use_freezed: bool,
+    pub proto_path: Option<String>,
+    pub proto_package: Option<String>,
+    pub proto_lock_path: Option<String>,
+    pub json_schema_path: Option<String>,
+    pub csharp_path: Option<String>,
+    pub csharp_namespace: Option<String>,
+    pub use_csharp_record: bool,
+    pub python_path: Option<String>,
+    pub use_python_dataclass: bool,
+    pub docs_path: Option<String>,
+    pub docs_format: DocsFormat,
+    pub diagram_path: Option<String>,
+    pub diagram_formats: Vec<DiagramFormat>,
+    pub diagram_group: DiagramGroup,
+    pub diagram_tables: Option<Vec<String>>,
+    pub diagram_keys_only: bool,
+    pub use_split_file: bool,
+    pub use_reexport: bool,
+    pub split_directorys: Vec<SplitDirectoryConfig>,
+    pub template: Option<TemplateConfig>,
 }
 
 fn use_signal_parser_default() -> bool {
@@ -108,6 +437,83 @@ fn use_split_file_default() -> bool {
     false
 }
 
+fn use_reexport_default() -> bool {
+    false
+}
+
+fn use_freezed_default() -> bool {
+    false
+}
+
+fn use_csharp_record_default() -> bool {
+    false
+}
+
+fn use_python_dataclass_default() -> bool {
+    false
+}
+
+pub fn diagram_formats_default() -> Vec<DiagramFormat> {
+    vec![DiagramFormat::Mermaid]
+}
+
+fn diagram_keys_only_default() -> bool {
+    false
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TemplateConfig {
+    #[serde(default = "template_directory_default")]
+    pub directory: String,
+    pub files: Vec<TemplateFileConfig>,
+}
+
+fn template_directory_default() -> String {
+    "templates".into()
+}
+
+/// A template of `TemplateConfig::directory`, rendered once per table or once per run.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TemplateFileConfig {
+    pub name: String,
+    /// Output path, rendered with the same context as the template.
+    pub path: String,
+    #[serde(default = "per_table_default")]
+    pub per_table: bool,
+}
+
+fn per_table_default() -> bool {
+    true
+}
+
+/// Field numbers of the proto messages by table config name, kept between runs so
+/// regeneration never renumbers a field.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ProtoLock {
+    #[serde(default)]
+    pub tables: BTreeMap<String, ProtoLockTable>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ProtoLockTable {
+    /// Field number by column name, numbers of dropped columns stay reserved.
+    #[serde(default)]
+    pub fields: BTreeMap<String, u32>,
+}
+
+/// A lookup table whose rows become a rust enum, `table` is `jkrtdb.dbo.rreference`
+/// or the config name `jkrtdb_dbo_rreference`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EnumConfig {
+    pub table: String,
+    /// Column of the enum values, integer or text.
+    pub key: String,
+    /// Column of the variant names.
+    pub name: String,
+    /// Defaults to the struct name with an `Enum` suffix.
+    pub enum_name: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DatabaseConfig {
     #[serde(default = "use_import_special_default")]
@@ -120,18 +526,77 @@ fn use_import_special_default() -> bool {
     false
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TableConfig {
     pub table_name: String,
     #[serde(default = "use_signal_parser_default")]
     pub use_signal_parser: bool,
     #[serde(default = "use_signal_file_default")]
     pub use_signal_file: bool,
+    #[serde(default = "use_dart_file_default")]
+    pub use_dart_file: bool,
     #[serde(default = "use_insert_query_default")]
     pub use_insert_query: bool,
+    #[serde(default = "use_try_from_row_default")]
+    pub use_try_from_row: bool,
+    #[serde(default = "use_crud_query_default")]
+    pub use_crud_query: bool,
+    #[serde(default = "use_bulk_insert_default")]
+    pub use_bulk_insert: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// `[[database.tables]]` and the cli flags, applied to the tables of a server or a
+/// snapshot.
+#[derive(Clone, Debug, Default)]
+pub struct TableOptions {
+    pub use_import_special: bool,
+    pub tables: HashMap<String, TableConfig>,
+    pub use_try_from_row: bool,
+    pub use_crud_query: bool,
+    pub use_bulk_insert: bool,
+}
+
+impl TableOptions {
+    pub fn from_args(args: &InnerArgs) -> Self {
+        let mut table_options = TableOptions {
+            use_try_from_row: args.use_try_from_row,
+            use_crud_query: args.use_crud_query,
+            use_bulk_insert: args.use_bulk_insert,
+            ..Default::default()
+        };
+        if let Some(database) = &args.database {
+            table_options.use_import_special = database.use_import_special;
+            for table in database.tables.iter().flatten() {
+                table_options
+                    .tables
+                    .insert(table.table_name.clone(), table.clone());
+            }
+        }
+        table_options
+    }
+
+    /// False for a table `use_import_special` leaves out.
+    pub fn is_imported(&self, table_name: &TableName) -> bool {
+        !self.use_import_special || self.tables.contains_key(&table_name.get_config_name())
+    }
+
+    /// Sets the `use_*` flags of `table` from its table config and the cli flags.
+    pub fn apply(&self, table: &mut Table) {
+        let table_config = self.tables.get(&table.name.get_config_name());
+        table.use_signal_parser = table_config.is_some_and(|config| config.use_signal_parser);
+        table.use_signal_file = table_config.is_none_or(|config| config.use_signal_file);
+        table.use_dart_file = table_config.is_none_or(|config| config.use_dart_file);
+        table.use_insert_query = table_config.is_none_or(|config| config.use_insert_query);
+        table.use_try_from_row =
+            table_config.is_some_and(|config| config.use_try_from_row) || self.use_try_from_row;
+        table.use_crud_query =
+            table_config.is_some_and(|config| config.use_crud_query) || self.use_crud_query;
+        table.use_bulk_insert =
+            table_config.is_some_and(|config| config.use_bulk_insert) || self.use_bulk_insert;
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct SplitDirectoryConfig {
     pub starts_with_name: String,
     pub directory_name: String,
@@ -144,3 +609,162 @@ fn use_signal_file_default() -> bool {
 fn use_insert_query_default() -> bool {
     true
 }
+
+fn use_try_from_row_default() -> bool {
+    false
+}
+
+fn use_crud_query_default() -> bool {
+    false
+}
+
+fn use_dart_file_default() -> bool {
+    true
+}
+
+fn use_bulk_insert_default() -> bool {
+    false
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct NamingConfig {
+    /// Suffix appended to field names that collide with Rust keywords.
+    /// When unset, keywords are written as raw identifiers (`r#type`).
+    pub keyword_suffix: Option<String>,
+    #[serde(default = "upper_first_case_default")]
+    pub struct_case: NameCase,
+    #[serde(default = "upper_first_case_default")]
+    pub field_case: NameCase,
+    #[serde(default = "file_case_default")]
+    pub file_case: NameCase,
+    #[serde(default = "const_case_default")]
+    pub const_case: NameCase,
+    #[serde(default = "include_catalog_default")]
+    pub include_catalog: bool,
+    #[serde(default)]
+    pub include_schema: IncludeSchema,
+    #[serde(default)]
+    pub struct_prefix: String,
+    #[serde(default)]
+    pub struct_suffix: String,
+    #[serde(default)]
+    pub field_prefix: String,
+    #[serde(default)]
+    pub field_suffix: String,
+    #[serde(default)]
+    pub file_prefix: String,
+    #[serde(default)]
+    pub file_suffix: String,
+    #[serde(default)]
+    pub on_collision: OnCollision,
+    #[serde(default)]
+    pub unicode: UnicodeMode,
+    /// SQL names mapped to the names used for Rust identifiers, `"가격" = "price"`.
+    #[serde(default)]
+    pub dictionary: HashMap<String, String>,
+}
+
+impl Default for NamingConfig {
+    fn default() -> Self {
+        NamingConfig {
+            keyword_suffix: None,
+            struct_case: upper_first_case_default(),
+            field_case: upper_first_case_default(),
+            file_case: file_case_default(),
+            const_case: const_case_default(),
+            include_catalog: include_catalog_default(),
+            include_schema: IncludeSchema::default(),
+            struct_prefix: String::new(),
+            struct_suffix: String::new(),
+            field_prefix: String::new(),
+            field_suffix: String::new(),
+            file_prefix: String::new(),
+            file_suffix: String::new(),
+            on_collision: OnCollision::default(),
+            unicode: UnicodeMode::default(),
+            dictionary: HashMap::new(),
+        }
+    }
+}
+
+impl NamingConfig {
+    /// `[naming]` of a config file of `--config_path`, the default naming when it has none.
+    pub fn from_config(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        #[derive(Deserialize)]
+        struct NamingTable {
+            naming: Option<NamingConfig>,
+        }
+
+        let path = path.as_ref();
+        let config = std::fs::read_to_string(path)
+            .map_err(|error| format!("failed to read config {:?}: {}", path, error))?;
+        let naming_table = toml::from_str::<NamingTable>(&config)
+            .map_err(|error| format!("failed to parse config {:?}: {}", path, error))?;
+        Ok(naming_table.naming.unwrap_or_default())
+    }
+}
+
+fn upper_first_case_default() -> NameCase {
+    NameCase::UpperFirst
+}
+
+fn file_case_default() -> NameCase {
+    NameCase::Lowercase
+}
+
+fn const_case_default() -> NameCase {
+    NameCase::Uppercase
+}
+
+fn include_catalog_default() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum NameCase {
+    /// Uppercases the first char of every part and keeps the rest, `jkrtdb_dbo_ritem` -> `JkrtdbDboRitem`.
+    UpperFirst,
+    #[serde(rename = "PascalCase")]
+    PascalCase,
+    #[serde(rename = "camelCase")]
+    CamelCase,
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+    #[serde(rename = "SCREAMING_SNAKE_CASE")]
+    ScreamingSnakeCase,
+    #[serde(rename = "lowercase")]
+    Lowercase,
+    #[serde(rename = "UPPERCASE")]
+    Uppercase,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IncludeSchema {
+    #[default]
+    Always,
+    Never,
+    NonDbo,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OnCollision {
+    /// Keeps the first name and renames the others deterministically.
+    #[default]
+    Rename,
+    /// Fails with a report of every colliding name.
+    Error,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UnicodeMode {
+    /// Replaces every non ASCII char with `_`.
+    #[default]
+    Replace,
+    /// Keeps chars that are valid in Rust identifiers(XID).
+    Keep,
+    /// Transliterates to ASCII, `가격` -> `gagyeog`.
+    Transliterate,
+}
@@ -0,0 +1,230 @@
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
+
+use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use serde_json::{json, Value};
+use tera::{Context, Tera};
+
+#[cfg(test)]
+use crate::helpers::fixtures::{make_table, resolve_tables};
+use crate::helpers::{
+    common::{get_field_type, get_rust_type, get_split_directory_name, quote_sql_identifier},
+    structs::{GeneratorOptions, SplitDirectoryConfig, Table, TemplateConfig},
+    traits::code_generator::CodeGenerator,
+};
+
+#[test]
+fn test_render_templates() {
+    let mut table_list = resolve_tables(
+        vec![make_table(
+            "ritem",
+            &[("ItemNo", "int", "NO"), ("Item Name", "nvarchar", "YES")],
+        )],
+        false,
+    );
+    table_list[0].primary_keys = vec!["ItemNo".into()];
+    table_list[0].description = Some("Menu items".into());
+    table_list[0].columns[0].description = Some("Item number".into());
+
+    let mut tera = make_tera(None).unwrap();
+    tera.add_raw_templates(vec![
+        (
+            "repository.rs",
+            "/// {{ table.description }}\npub struct {{ table.struct_name }}Repository;\n{% for column in table.columns %}// {{ column.field_name | snake_case }}: {{ column.field_type }} {{ column.column_name | sql_identifier }}{% if column.description %} {{ column.description }}{% endif %}\n{% endfor %}",
+        ),
+        (
+            "tables.sql",
+            "{% for table in tables %}DROP TABLE {{ table.sql_table_name }};\n{% endfor %}",
+        ),
+    ])
+    .unwrap();
+    let template = TemplateConfig {
+        directory: "templates".into(),
+        files: vec![
+            crate::helpers::structs::TemplateFileConfig {
+                name: "repository.rs".into(),
+                path: "out/{{ table.file_name }}_repository.rs".into(),
+                per_table: true,
+            },
+            crate::helpers::structs::TemplateFileConfig {
+                name: "tables.sql".into(),
+                path: "out/tables.sql".into(),
+                per_table: false,
+            },
+        ],
+    };
+    let file_list = render_templates(&mut tera, &template, &table_list, &[]).unwrap();
+    let file = &file_list[&PathBuf::from("out/jkrtdb_dbo_ritem_repository.rs")];
+    assert!(file.starts_with("/// Menu items\npub struct JkrtdbDboRitemRepository;\n"));
+    assert!(file.contains("// item_no: i32 [ItemNo] Item number\n"));
+    assert!(file.contains("// item_name: Option<String> [Item Name]\n"));
+    assert_eq!(
+        file_list[&PathBuf::from("out/tables.sql")],
+        "DROP TABLE [jkrtdb].[dbo].[ritem];\n"
+    );
+}
+
+/// User templates of `[template]`, nothing when it is not configured.
+pub struct TemplateGenerator;
+
+impl CodeGenerator for TemplateGenerator {
+    fn name(&self) -> &'static str {
+        "templates"
+    }
+
+    fn generate(
+        &self,
+        table_list: &[Table],
+        options: &GeneratorOptions,
+    ) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+        match &options.template {
+            Some(template) => {
+                let mut tera = make_tera(Some(&template.directory))?;
+                render_templates(&mut tera, template, table_list, &options.split_directorys)
+            }
+            None => Ok(HashMap::new()),
+        }
+    }
+}
+
+fn render_templates(
+    tera: &mut Tera,
+    template: &TemplateConfig,
+    table_list: &[Table],
+    split_directorys: &[SplitDirectoryConfig],
+) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+    let tables: Vec<Value> = table_list
+        .iter()
+        .map(|table| make_table_context(table, split_directorys))
+        .collect();
+    let mut file_list: HashMap<PathBuf, String> = HashMap::new();
+
+    for (index, file) in template.files.iter().enumerate() {
+        let path_name = format!("__path_{}", index);
+        tera.add_raw_template(&path_name, &file.path)?;
+
+        let mut contexts = vec![];
+        match file.per_table {
+            true => {
+                for table in &tables {
+                    let mut context = Context::new();
+                    context.insert("table", table);
+                    context.insert("tables", &tables);
+                    contexts.push(context);
+                }
+            }
+            false => {
+                let mut context = Context::new();
+                context.insert("tables", &tables);
+                contexts.push(context);
+            }
+        }
+
+        for context in contexts {
+            let path = PathBuf::from_str(tera.render(&path_name, &context)?.trim())?;
+            let rendered = tera.render(&file.name, &context).map_err(|error| {
+                format!("failed to render {} to {:?}: {:?}", file.name, path, error)
+            })?;
+            if file_list.insert(path.clone(), rendered).is_some() {
+                return Err(
+                    format!("template {} renders {:?} more than once", file.name, path).into(),
+                );
+            }
+        }
+    }
+    Ok(file_list)
+}
+
+/// Loads the templates of `directory` and registers the naming and type filters.
+fn make_tera(directory: Option<&str>) -> Result<Tera, Box<dyn std::error::Error>> {
+    let mut tera = match directory {
+        Some(directory) => Tera::new(&format!("{}/**/*", directory.trim_end_matches(['/', '\\'])))?,
+        None => Tera::default(),
+    };
+    // Outputs are code, not html.
+    tera.autoescape_on(vec![]);
+
+    for name in [
+        "snake_case",
+        "pascal_case",
+        "camel_case",
+        "screaming_snake_case",
+    ] {
+        tera.register_filter(
+            name,
+            move |value: &Value, _: &HashMap<String, Value>| -> tera::Result<Value> {
+                let text = tera::try_get_value!(name, "value", String, value);
+                Ok(Value::String(match name {
+                    "snake_case" => text.to_snake_case(),
+                    "pascal_case" => text.to_upper_camel_case(),
+                    "camel_case" => text.to_lower_camel_case(),
+                    _ => text.to_shouty_snake_case(),
+                }))
+            },
+        );
+    }
+    tera.register_filter(
+        "rust_type",
+        |value: &Value, _: &HashMap<String, Value>| -> tera::Result<Value> {
+            let data_type = tera::try_get_value!("rust_type", "value", String, value);
+            Ok(Value::String(get_rust_type(&data_type).into()))
+        },
+    );
+    tera.register_filter(
+        "sql_identifier",
+        |value: &Value, _: &HashMap<String, Value>| -> tera::Result<Value> {
+            let text = tera::try_get_value!("sql_identifier", "value", String, value);
+            Ok(Value::String(quote_sql_identifier(&text)))
+        },
+    );
+    Ok(tera)
+}
+
+/// `Table` with the resolved names and mapped rust types, which are not serialized.
+fn make_table_context(table: &Table, split_directorys: &[SplitDirectoryConfig]) -> Value {
+    let mut columns: Vec<_> = table.columns.iter().collect();
+    columns.sort_by_key(|column| column.ordinal_position);
+    let columns: Vec<Value> = columns
+        .into_iter()
+        .map(|column| {
+            json!({
+                "column_name": column.column_name,
+                "field_name": column.field_name,
+                "ordinal_position": column.ordinal_position,
+                "data_type": column.data_type,
+                "rust_type": get_rust_type(&column.data_type),
+                "field_type": get_field_type(column),
+                "is_nullable": column.is_nullable == "YES",
+                "is_primary_key": table.primary_keys.contains(&column.column_name),
+                "is_identity": column.is_identity,
+                "is_computed": column.is_computed,
+                "is_server_generated": column.is_server_generated(),
+                "character_maximum_length": column.character_maximum_length,
+                "numeric_precision": column.numeric_precision,
+                "numeric_scale": column.numeric_scale,
+                "column_default": column.column_default,
+                "description": column.description,
+            })
+        })
+        .collect();
+
+    json!({
+        "table_catalog": table.name.table_catalog,
+        "table_schema": table.name.table_schema,
+        "table_name": table.name.table_name,
+        "table_type": table.name.table_type,
+        "sql_table_name": format!(
+            "{}.{}.{}",
+            quote_sql_identifier(&table.name.table_catalog),
+            quote_sql_identifier(&table.name.table_schema),
+            quote_sql_identifier(&table.name.table_name)
+        ),
+        "config_name": table.name.get_config_name(),
+        "struct_name": table.idents.struct_name,
+        "file_name": table.idents.file_name,
+        "const_name": table.idents.const_name,
+        "directory_name": get_split_directory_name(table, split_directorys),
+        "primary_keys": table.primary_keys,
+        "description": table.description,
+        "columns": columns,
+    })
+}
@@ -0,0 +1,15 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use crate::helpers::structs::{GeneratorOptions, Table};
+
+/// An output backend, renders the tables into files keyed by their path.
+pub trait CodeGenerator {
+    /// Name used in `generators = [...]`.
+    fn name(&self) -> &'static str;
+
+    fn generate(
+        &self,
+        table_list: &[Table],
+        options: &GeneratorOptions,
+    ) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>>;
+}
@@ -1,3 +1,5 @@
+pub mod code_generator;
+#[cfg(feature = "connect")]
 pub mod select_parser;
 
 pub trait StringUtil {
@@ -0,0 +1,207 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+
+#[cfg(test)]
+use crate::helpers::fixtures::{make_table, resolve_tables};
+use crate::helpers::{
+    common::{get_output_path, get_rust_type, get_split_file_path, get_table_names, make_comment},
+    config::STRUCT_TYPESCRIPT_FILE_NAME,
+    structs::{ColumnName, GeneratorOptions, SplitDirectoryConfig, Table},
+    traits::code_generator::CodeGenerator,
+};
+
+#[test]
+fn test_make_interface() {
+    let table_list = resolve_tables(
+        vec![make_table(
+            "ritem",
+            &[
+                ("ItemNo", "int", "NO"),
+                ("Item Name", "nvarchar", "YES"),
+                ("IsUsed", "bit", "NO"),
+                ("RegDate", "datetime", "YES"),
+                ("Photo", "image", "YES"),
+            ],
+        )],
+        true,
+    );
+
+    let file = make_interface("JkrtdbDboRitem", &table_list[0]);
+    assert!(file.starts_with("export interface JkrtdbDboRitem {\n  ItemNo: number;\n"));
+    assert!(file.contains("  \"Item Name\": string | null;\n"));
+    assert!(file.contains("  IsUsed: boolean;\n"));
+    // `ts_seconds_option` writes epoch seconds.
+    assert!(file.contains("  RegDate: number | null;\n"));
+    assert!(file.contains("  Photo: number[] | null;\n"));
+
+    let split_directorys = vec![SplitDirectoryConfig {
+        starts_with_name: "jkrtdb_dbo_r".into(),
+        directory_name: "item".into(),
+    }];
+    let file_list =
+        make_ts_split_files(&Some("web".into()), &table_list, &split_directorys).unwrap();
+    assert!(file_list.contains_key(&PathBuf::from("web/item/jkrtdb_dbo_ritem.ts")));
+    assert_eq!(
+        file_list[&PathBuf::from("web/item/index.ts")],
+        "export * from './jkrtdb_dbo_ritem';\n"
+    );
+    assert_eq!(
+        file_list[&PathBuf::from("web/index.ts")],
+        "export * from './item';\n"
+    );
+}
+
+/// TypeScript interfaces of every table, matching the json of the rust structs.
+pub struct TypeScriptGenerator;
+
+impl CodeGenerator for TypeScriptGenerator {
+    fn name(&self) -> &'static str {
+        "typescript"
+    }
+
+    fn generate(
+        &self,
+        table_list: &[Table],
+        options: &GeneratorOptions,
+    ) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+        match options.use_split_file {
+            true => make_ts_split_files(
+                &options.typescript_path,
+                table_list,
+                &options.split_directorys,
+            ),
+            false => make_ts_one_file(&options.typescript_path, table_list),
+        }
+    }
+}
+
+fn make_ts_one_file(
+    path: &Option<String>,
+    table_list: &[Table],
+) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+    let path = get_output_path(path, STRUCT_TYPESCRIPT_FILE_NAME)?;
+    let interfaces: Vec<String> = table_list
+        .iter()
+        .map(|table| {
+            let (table_name, _, _) = get_table_names(table);
+            make_interface(&table_name, table)
+        })
+        .collect();
+    Ok(HashMap::from([(path, interfaces.join("\n"))]))
+}
+
+fn make_ts_split_files(
+    path: &Option<String>,
+    table_list: &[Table],
+    split_directorys: &[SplitDirectoryConfig],
+) -> Result<HashMap<PathBuf, String>, Box<dyn std::error::Error>> {
+    let path = get_output_path(path, "typescript")?;
+    let mut file_list: HashMap<PathBuf, String> = HashMap::new();
+
+    for table in table_list {
+        let (table_name, file_name, _) = get_table_names(table);
+        let current_path =
+            get_split_file_path(&path, table, split_directorys, &format!("{}.ts", file_name));
+        file_list.insert(current_path, make_interface(&table_name, table));
+    }
+    let file_paths: Vec<PathBuf> = file_list.keys().cloned().collect();
+    file_list.extend(make_index_files(&path, &file_paths));
+    Ok(file_list)
+}
+
+/// Returns `index.ts` of `root` and every directory between it and the files, with
+/// `export *` of each child.
+fn make_index_files(root: &Path, file_paths: &[PathBuf]) -> HashMap<PathBuf, String> {
+    let mut tree: BTreeMap<PathBuf, BTreeSet<String>> = BTreeMap::new();
+    tree.entry(root.to_path_buf()).or_default();
+    for file_path in file_paths {
+        let mut path = file_path.with_extension("");
+        while let Some(parent) = path.parent() {
+            if !parent.starts_with(root) {
+                break;
+            }
+            let module_name = path.file_name().unwrap().to_string_lossy().to_string();
+            tree.entry(parent.to_path_buf())
+                .or_default()
+                .insert(module_name);
+            path = parent.to_path_buf();
+        }
+    }
+
+    tree.into_iter()
+        .map(|(directory, modules)| {
+            let file: String = modules
+                .iter()
+                .map(|module_name| format!("export * from './{}';\n", module_name))
+                .collect();
+            (directory.join("index.ts"), file)
+        })
+        .collect()
+}
+
+fn make_interface(table_name: &str, table: &Table) -> String {
+    let mut file = String::new();
+    file.push_str(&make_jsdoc(&table.description, ""));
+    file.push_str(&format!("export interface {} {{\n", table_name));
+    for column in &table.columns {
+        file.push_str(&make_jsdoc(&column.description, "  "));
+        file.push_str(&format!(
+            "  {}: {};\n",
+            get_property_name(&column.column_name),
+            get_typescript_field_type(column)
+        ));
+    }
+    file.push_str("}\n");
+    file
+}
+
+fn make_jsdoc(description: &Option<String>, indent: &str) -> String {
+    match description {
+        Some(description) => format!(
+            "{0}/**\n{1}{0} */\n",
+            indent,
+            make_comment(
+                &Some(description.replace("*/", "*\\/")),
+                &format!("{} * ", indent)
+            )
+        ),
+        None => String::new(),
+    }
+}
+
+/// The rust structs keep the original column names with serde rename, so they are the
+/// json keys. Names that are not identifiers are quoted.
+fn get_property_name(column_name: &str) -> String {
+    let is_identifier = column_name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && column_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    match is_identifier {
+        true => column_name.into(),
+        false => serde_json::to_string(column_name).unwrap(),
+    }
+}
+
+/// TypeScript type of the json the rust type serializes to, datetime is epoch seconds
+/// of `ts_seconds`.
+pub fn get_typescript_type(data_type: &str) -> &'static str {
+    match get_rust_type(data_type) {
+        "bool" => "boolean",
+        "u8" | "i16" | "i32" | "i64" | "f32" | "f64" | "DateTime<Utc>" => "number",
+        "Vec<u8>" => "number[]",
+        _ => "string",
+    }
+}
+
+fn get_typescript_field_type(column: &ColumnName) -> String {
+    let data_type = get_typescript_type(&column.data_type);
+    match column.is_nullable.as_str() == "YES" {
+        true => format!("{} | null", data_type),
+        false => data_type.into(),
+    }
+}
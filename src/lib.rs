@@ -0,0 +1,11 @@
+//! Generates rust structs and other outputs from the tables of SQL Server.
+//!
+//! The `mssql_tiberius_schema_parser` binary is a wrapper over [`Schema`], which can be used
+//! from `build.rs` or other tools to generate files in memory or write them.
+pub mod helpers;
+
+pub use helpers::{
+    schema::Schema,
+    structs::{GeneratorOptions, InnerArgs, NamingConfig, Table},
+    traits::code_generator::CodeGenerator,
+};
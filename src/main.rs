@@ -1,8 +1,5 @@
-use crate::helpers::args_parser::Cli;
 use clap::Parser;
-use helpers::auth_handler::auth_handler;
-
-mod helpers;
+use mssql_tiberius_schema_parser::helpers::{args_parser::Cli, auth_handler::auth_handler};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {